@@ -0,0 +1,243 @@
+//! HTTP access to the statsapi, with retries and rate limiting.
+
+use std::collections::hash_map::RandomState;
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Certificate, Proxy, StatusCode};
+use serde::Deserialize;
+
+use crate::error::Error;
+
+/// Production statsapi host.
+pub const DEFAULT_BASE_URL: &str = "https://statsapi.mlb.com";
+const DEFAULT_USER_AGENT: &str = "pitchers-cli/0.1";
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_RETRIES: u32 = 3;
+const DEFAULT_BACKOFF_MS: u64 = 500;
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(120);
+
+/// HTTP settings, as read from the `[http]` table of the config file or
+/// assembled from command-line flags and environment variables.
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct HttpSettings {
+    /// API host, e.g. `https://statsapi.mlb.com`.
+    pub base_url: Option<String>,
+    /// Whole-request timeout, seconds.
+    pub timeout: Option<u64>,
+    /// Connect timeout, seconds.
+    pub connect_timeout: Option<u64>,
+    /// Proxy URL for all requests.
+    pub proxy: Option<String>,
+    /// Extra PEM bundle of trusted root certificates.
+    pub ca_cert: Option<PathBuf>,
+    /// `User-Agent` header.
+    pub user_agent: Option<String>,
+    /// Retries after the first attempt.
+    pub retries: Option<u32>,
+    /// Base delay of the exponential backoff, milliseconds.
+    pub backoff_ms: Option<u64>,
+    /// Cap on requests per second.
+    pub max_rps: Option<f64>,
+}
+
+impl HttpSettings {
+    /// Fill any unset value from `fallback`.
+    pub fn or(self, fallback: HttpSettings) -> HttpSettings {
+        HttpSettings {
+            base_url: self.base_url.or(fallback.base_url),
+            timeout: self.timeout.or(fallback.timeout),
+            connect_timeout: self.connect_timeout.or(fallback.connect_timeout),
+            proxy: self.proxy.or(fallback.proxy),
+            ca_cert: self.ca_cert.or(fallback.ca_cert),
+            user_agent: self.user_agent.or(fallback.user_agent),
+            retries: self.retries.or(fallback.retries),
+            backoff_ms: self.backoff_ms.or(fallback.backoff_ms),
+            max_rps: self.max_rps.or(fallback.max_rps),
+        }
+    }
+}
+
+/// A configured HTTP client bound to a statsapi base URL.
+///
+/// Every request goes through [`Api::get`], which retries transient failures
+/// with exponential backoff and spaces requests out to honor `max-rps`.
+pub struct Api {
+    client: Client,
+    base_url: String,
+    retries: u32,
+    backoff: Duration,
+    min_interval: Option<Duration>,
+    /// When the latest request was (or is scheduled to be) sent; shared by all threads.
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl Api {
+    /// Build a client from `settings`, using defaults for anything unset.
+    pub fn new(settings: &HttpSettings) -> Result<Self> {
+        let mut builder = Client::builder()
+            .user_agent(settings.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT))
+            .timeout(Duration::from_secs(settings.timeout.unwrap_or(DEFAULT_TIMEOUT_SECS)));
+
+        if let Some(secs) = settings.connect_timeout {
+            builder = builder.connect_timeout(Duration::from_secs(secs));
+        }
+        if let Some(proxy) = &settings.proxy {
+            builder = builder.proxy(Proxy::all(proxy).with_context(|| format!("invalid proxy '{}'", proxy))?);
+        }
+        if let Some(path) = &settings.ca_cert {
+            let pem = fs::read(path).with_context(|| format!("reading CA bundle {}", path.display()))?;
+            for cert in Certificate::from_pem_bundle(&pem)
+                .with_context(|| format!("parsing CA bundle {}", path.display()))?
+            {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+
+        let base_url = settings
+            .base_url
+            .as_deref()
+            .unwrap_or(DEFAULT_BASE_URL)
+            .trim_end_matches('/')
+            .to_string();
+
+        let min_interval = match settings.max_rps.filter(|rps| *rps > 0.0) {
+            Some(rps) => Some(
+                Duration::try_from_secs_f64(1.0 / rps).map_err(|_| anyhow!("max-rps {:e} is too small", rps))?,
+            ),
+            None => None,
+        };
+
+        Ok(Api {
+            client: builder.build()?,
+            base_url,
+            retries: settings.retries.unwrap_or(DEFAULT_RETRIES),
+            backoff: Duration::from_millis(settings.backoff_ms.unwrap_or(DEFAULT_BACKOFF_MS)),
+            min_interval,
+            last_request: Mutex::new(None),
+        })
+    }
+
+    /// GET `url`, retrying timeouts, connection errors, 429 and 5xx responses.
+    ///
+    /// Any other response is returned as-is (including 304 and 4xx) so callers
+    /// can inspect it; an error is returned once retries are exhausted.
+    pub fn get(&self, url: &str, headers: HeaderMap) -> Result<Response> {
+        let attempts = self.retries.saturating_add(1);
+        for attempt in 0..attempts {
+            let last = attempt + 1 == attempts;
+            self.throttle();
+
+            let delay = match self.client.get(url).headers(headers.clone()).send() {
+                Ok(resp) if is_retryable_status(resp.status()) => {
+                    if last {
+                        return resp
+                            .error_for_status()
+                            .map_err(Error::from)
+                            .with_context(|| format!("giving up after {} attempt(s)", attempts));
+                    }
+                    retry_after(&resp).unwrap_or_else(|| self.backoff_delay(attempt))
+                }
+                Ok(resp) => return Ok(resp),
+                Err(e) if !last && (e.is_timeout() || e.is_connect() || e.is_request()) => {
+                    self.backoff_delay(attempt)
+                }
+                Err(e) => {
+                    return Err(Error::from(e))
+                        .with_context(|| format!("giving up after {} attempt(s)", attempt + 1))
+                }
+            };
+            thread::sleep(delay);
+        }
+        unreachable!("the last attempt always returns")
+    }
+
+    /// Absolute URL for an API path such as `/api/v1/schedule`.
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// Sleep until at least `min_interval` has passed since the previous request,
+    /// from any thread.
+    fn throttle(&self) {
+        let Some(interval) = self.min_interval else {
+            return;
+        };
+        let now = Instant::now();
+        let wait = {
+            let mut last = self.last_request.lock().unwrap_or_else(|e| e.into_inner());
+            // take the next free slot, then sleep without holding the lock
+            let slot = last
+                .and_then(|prev| prev.checked_add(interval))
+                .map_or(now, |next| next.max(now));
+            *last = Some(slot);
+            slot - now
+        };
+        if !wait.is_zero() {
+            thread::sleep(wait);
+        }
+    }
+
+    /// Exponential backoff with full jitter: a random delay in `[0, backoff * 2^attempt]`.
+    fn backoff_delay(&self, attempt: u32) -> Duration {
+        let ceiling = self
+            .backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(MAX_BACKOFF);
+        let random = RandomState::new().build_hasher().finish();
+        ceiling.mul_f64((random % 10_000) as f64 / 10_000.0)
+    }
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// The server's `Retry-After` hint, when given in seconds.
+fn retry_after(resp: &Response) -> Option<Duration> {
+    let secs: u64 = resp.headers().get(RETRY_AFTER)?.to_str().ok()?.trim().parse().ok()?;
+    Some(Duration::from_secs(secs).min(MAX_RETRY_AFTER))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_can_be_shared_across_threads() {
+        fn assert_sync<T: Send + Sync>() {}
+        assert_sync::<Api>();
+    }
+
+    #[test]
+    fn tiny_max_rps_is_an_error() {
+        let settings = HttpSettings {
+            max_rps: Some(1e-30),
+            ..HttpSettings::default()
+        };
+        assert!(Api::new(&settings).is_err());
+    }
+
+    #[test]
+    fn throttle_spaces_requests() {
+        let settings = HttpSettings {
+            max_rps: Some(50.0),
+            ..HttpSettings::default()
+        };
+        let api = Api::new(&settings).unwrap();
+        let start = Instant::now();
+        for _ in 0..3 {
+            api.throttle();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+}
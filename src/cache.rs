@@ -0,0 +1,270 @@
+//! On-disk cache of game feeds.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::model::{self, GameFeed};
+
+/// On-disk store of game feeds, one `<gamePk>.json` file per game.
+///
+/// Validators from the last response (ETag / Last-Modified) live next to the
+/// feed in `<gamePk>.meta` so in-progress games can be revalidated cheaply.
+pub struct FeedCache {
+    dir: PathBuf,
+}
+
+/// A feed read back from the cache.
+pub struct CachedFeed {
+    /// The parsed feed.
+    pub feed: GameFeed,
+    /// `ETag` of the response it came from.
+    pub etag: Option<String>,
+    /// `Last-Modified` of the response it came from.
+    pub last_modified: Option<String>,
+}
+
+/// One line of `cache list` output.
+pub struct CacheEntry {
+    /// Game id.
+    pub game_pk: u64,
+    /// Detailed game state, e.g. "Final".
+    pub state: String,
+    /// "Away @ Home".
+    pub matchup: String,
+    /// Official date, YYYY-MM-DD.
+    pub date: String,
+    /// Size of the cached feed.
+    pub bytes: u64,
+}
+
+impl FeedCache {
+    /// A cache in `dir`, or in `$XDG_CACHE_HOME/pitchers` (`~/.cache/pitchers`).
+    pub fn new(dir: Option<PathBuf>) -> Result<Self> {
+        let dir = match dir {
+            Some(d) => d,
+            None => default_dir().context("cannot determine a cache directory; use --cache-dir")?,
+        };
+        Ok(FeedCache { dir })
+    }
+
+    /// Directory holding the cached feeds.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The cached feed of a game, if any; a corrupt entry counts as missing.
+    pub fn load(&self, game_pk: u64) -> Result<Option<CachedFeed>> {
+        let path = self.feed_path(game_pk);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let text = fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+        let Ok(feed) = model::parse_feed(&text) else {
+            // a truncated or corrupt entry is treated as a miss and overwritten
+            return Ok(None);
+        };
+
+        let meta: Value = fs::read_to_string(self.meta_path(game_pk))
+            .ok()
+            .and_then(|m| serde_json::from_str(&m).ok())
+            .unwrap_or(Value::Null);
+        let header = |key: &str| meta.get(key).and_then(|v| v.as_str()).map(str::to_string);
+
+        Ok(Some(CachedFeed {
+            feed,
+            etag: header("etag"),
+            last_modified: header("lastModified"),
+        }))
+    }
+
+    /// Save a feed and its response validators, replacing any previous copy.
+    pub fn store(
+        &self,
+        game_pk: u64,
+        text: &str,
+        etag: Option<&str>,
+        last_modified: Option<&str>,
+    ) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("creating cache directory {}", self.dir.display()))?;
+
+        let path = self.feed_path(game_pk);
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, text).with_context(|| format!("writing {}", tmp.display()))?;
+        fs::rename(&tmp, &path).with_context(|| format!("writing {}", path.display()))?;
+
+        let meta = serde_json::json!({ "etag": etag, "lastModified": last_modified });
+        fs::write(self.meta_path(game_pk), meta.to_string())?;
+        Ok(())
+    }
+
+    /// Every cached game, by game id.
+    pub fn list(&self) -> Result<Vec<CacheEntry>> {
+        let mut entries = Vec::new();
+        for game_pk in self.game_pks()? {
+            let path = self.feed_path(game_pk);
+            let bytes = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            let feed: Value = fs::read_to_string(&path)
+                .ok()
+                .and_then(|t| serde_json::from_str(&t).ok())
+                .unwrap_or(Value::Null);
+
+            let game_data = feed.get("gameData");
+            let str_at = |ptr: &str| {
+                game_data
+                    .and_then(|g| g.pointer(ptr))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("?")
+                    .to_string()
+            };
+            entries.push(CacheEntry {
+                game_pk,
+                state: str_at("/status/detailedState"),
+                matchup: format!("{} @ {}", str_at("/teams/away/name"), str_at("/teams/home/name")),
+                date: str_at("/datetime/officialDate"),
+                bytes,
+            });
+        }
+        entries.sort_by_key(|e| e.game_pk);
+        Ok(entries)
+    }
+
+    /// Remove cached feeds of games that are not final (or every feed with `all`).
+    /// Returns the number of games removed.
+    pub fn prune(&self, all: bool) -> Result<usize> {
+        let mut removed = 0;
+        for game_pk in self.game_pks()? {
+            let keep = !all
+                && self
+                    .load(game_pk)?
+                    .is_some_and(|cached| cached.feed.is_final());
+            if !keep {
+                fs::remove_file(self.feed_path(game_pk))?;
+                let _ = fs::remove_file(self.meta_path(game_pk));
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    fn game_pks(&self) -> Result<Vec<u64>> {
+        let mut pks = Vec::new();
+        let Ok(read_dir) = fs::read_dir(&self.dir) else {
+            return Ok(pks);
+        };
+        for entry in read_dir {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            if let Some(pk) = path.file_stem().and_then(|s| s.to_str()).and_then(|s| s.parse().ok()) {
+                pks.push(pk);
+            }
+        }
+        Ok(pks)
+    }
+
+    fn feed_path(&self, game_pk: u64) -> PathBuf {
+        self.dir.join(format!("{}.json", game_pk))
+    }
+
+    fn meta_path(&self, game_pk: u64) -> PathBuf {
+        self.dir.join(format!("{}.meta", game_pk))
+    }
+}
+
+fn default_dir() -> Option<PathBuf> {
+    let base = match env::var_os("XDG_CACHE_HOME").filter(|v| !v.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".cache"),
+    };
+    Some(base.join("pitchers"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process;
+
+    use serde_json::json;
+
+    use super::*;
+
+    /// A cache in a fresh directory of its own under the system temp dir.
+    fn cache(name: &str) -> FeedCache {
+        let dir = env::temp_dir().join(format!("pitchers-cache-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        FeedCache::new(Some(dir)).unwrap()
+    }
+
+    fn feed(state: &str, detailed: &str) -> String {
+        json!({
+            "gameData": {
+                "status": { "abstractGameState": state, "detailedState": detailed },
+                "teams": { "away": { "name": "Riverside Otters" }, "home": { "name": "Harbor City Gulls" } },
+                "datetime": { "officialDate": "2025-06-01" },
+            },
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn stored_feeds_load_back_with_their_validators() {
+        let cache = cache("round-trip");
+        assert!(cache.load(1).unwrap().is_none());
+
+        cache.store(1, &feed("Final", "Final"), Some("\"abc\""), None).unwrap();
+        let cached = cache.load(1).unwrap().unwrap();
+        assert!(cached.feed.is_final());
+        assert_eq!(cached.etag.as_deref(), Some("\"abc\""));
+        assert_eq!(cached.last_modified, None);
+
+        // a new copy replaces the old one, validators included
+        cache.store(1, &feed("Live", "In Progress"), None, Some("Sun, 01 Jun 2025 23:00:00 GMT")).unwrap();
+        let cached = cache.load(1).unwrap().unwrap();
+        assert!(!cached.feed.is_final());
+        assert_eq!(cached.etag, None);
+        assert_eq!(cached.last_modified.as_deref(), Some("Sun, 01 Jun 2025 23:00:00 GMT"));
+        fs::remove_dir_all(cache.dir()).unwrap();
+    }
+
+    #[test]
+    fn corrupt_entries_are_misses() {
+        let cache = cache("corrupt");
+        cache.store(1, &feed("Final", "Final"), None, None).unwrap();
+        fs::write(cache.dir().join("1.json"), "{\"gameData\": {\"sta").unwrap();
+        assert!(cache.load(1).unwrap().is_none());
+        fs::remove_dir_all(cache.dir()).unwrap();
+    }
+
+    #[test]
+    fn list_and_prune() {
+        let cache = cache("prune");
+        assert!(cache.list().unwrap().is_empty());
+        assert_eq!(cache.prune(false).unwrap(), 0);
+
+        cache.store(3, &feed("Live", "In Progress"), None, None).unwrap();
+        cache.store(1, &feed("Final", "Final"), None, None).unwrap();
+        cache.store(2, "not a feed", None, None).unwrap();
+        fs::write(cache.dir().join("notes.txt"), "not a game").unwrap();
+
+        let entries = cache.list().unwrap();
+        let listed: Vec<(u64, &str)> = entries.iter().map(|e| (e.game_pk, e.state.as_str())).collect();
+        assert_eq!(listed, [(1, "Final"), (2, "?"), (3, "In Progress")]);
+        assert_eq!(entries[0].matchup, "Riverside Otters @ Harbor City Gulls");
+        assert_eq!(entries[0].date, "2025-06-01");
+        assert_eq!(entries[1].bytes, "not a feed".len() as u64);
+
+        // final games stay, the rest goes, meta files included
+        assert_eq!(cache.prune(false).unwrap(), 2);
+        assert_eq!(cache.game_pks().unwrap(), [1]);
+        assert!(!cache.meta_path(3).exists());
+
+        assert_eq!(cache.prune(true).unwrap(), 1);
+        assert!(cache.list().unwrap().is_empty());
+        fs::remove_dir_all(cache.dir()).unwrap();
+    }
+}
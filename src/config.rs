@@ -0,0 +1,165 @@
+//! The `config.toml` file.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use crate::api::HttpSettings;
+use crate::scheme::Scheme;
+
+/// Contents of `config.toml`. Every section is optional.
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// The `[http]` table.
+    #[serde(default)]
+    pub http: HttpSettings,
+    /// Pitch category scheme used when `--scheme` is not given.
+    pub scheme: Option<String>,
+    /// User-defined pitch category schemes, by name.
+    #[serde(default)]
+    pub schemes: BTreeMap<String, Scheme>,
+}
+
+impl Config {
+    /// Load the config file at `path`, or from the default location if none
+    /// is given. A missing default file is not an error.
+    pub fn load(path: Option<&Path>) -> Result<Config> {
+        let (path, required) = match path {
+            Some(p) => (p.to_path_buf(), true),
+            None => match default_path() {
+                Some(p) => (p, false),
+                None => return Ok(Config::default()),
+            },
+        };
+
+        if !required && !path.exists() {
+            return Ok(Config::default());
+        }
+        let text = fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    /// The scheme called `name`, else the one the config selects, else "default".
+    /// A `[schemes.default]` table replaces the built-in scheme.
+    pub fn scheme(&self, name: Option<&str>) -> Result<Scheme> {
+        let name = name.or(self.scheme.as_deref()).unwrap_or("default");
+        if let Some(scheme) = self.schemes.get(name) {
+            scheme.validate(name)?;
+            return Ok(scheme.clone());
+        }
+        if name == "default" {
+            return Ok(Scheme::default());
+        }
+
+        let mut available: Vec<&str> = self.schemes.keys().map(String::as_str).collect();
+        if !available.contains(&"default") {
+            available.insert(0, "default");
+        }
+        bail!("unknown scheme '{}' (available: {})", name, available.join(", "))
+    }
+}
+
+fn default_path() -> Option<PathBuf> {
+    let base = match env::var_os("XDG_CONFIG_HOME").filter(|v| !v.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(base.join("pitchers").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process;
+
+    use super::*;
+
+    /// Write `text` to a config file of its own under the system temp dir.
+    fn config_file(name: &str, text: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("pitchers-config-{}-{}.toml", name, process::id()));
+        fs::write(&path, text).unwrap();
+        path
+    }
+
+    #[test]
+    fn load_reads_the_http_table() {
+        let path = config_file(
+            "http",
+            "[http]\nbase-url = \"http://127.0.0.1:8080\"\ntimeout = 5\nmax-rps = 2.5\n",
+        );
+        let config = Config::load(Some(&path)).unwrap();
+        assert_eq!(config.http.base_url.as_deref(), Some("http://127.0.0.1:8080"));
+        assert_eq!(config.http.timeout, Some(5));
+        assert_eq!(config.http.max_rps, Some(2.5));
+        assert_eq!(config.http.proxy, None);
+        fs::remove_file(path).unwrap();
+
+        let path = config_file("empty", "");
+        assert!(Config::load(Some(&path)).unwrap().http.base_url.is_none());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn load_rejects_missing_files_and_unknown_keys() {
+        let missing = env::temp_dir().join(format!("pitchers-config-missing-{}.toml", process::id()));
+        assert!(Config::load(Some(&missing)).is_err());
+
+        let path = config_file("unknown", "[http]\ntimeout-secs = 5\n");
+        let err = format!("{:#}", Config::load(Some(&path)).err().unwrap());
+        assert!(err.contains("timeout-secs"), "{}", err);
+        fs::remove_file(path).unwrap();
+    }
+    fn parse(text: &str) -> Config {
+        toml::from_str(text).unwrap()
+    }
+
+    const ANALYST: &str = r#"
+        [[schemes.analyst.categories]]
+        name = "fastballs"
+        pitches = ["FF", "SI"]
+
+        [[schemes.analyst.categories]]
+        name = "spin"
+        pitches = ["slider", "CU"]
+    "#;
+
+    #[test]
+    fn scheme_by_flag_then_config_then_default() {
+        let names = |config: &Config, name| config.scheme(name).unwrap().category_names();
+        let builtin = Scheme::default().category_names();
+
+        let config = parse(ANALYST);
+        assert_eq!(names(&config, None), builtin);
+        assert_eq!(names(&config, Some("default")), builtin);
+        assert_eq!(names(&config, Some("analyst")), ["fastballs", "spin", "other", "unclassified"]);
+
+        let config = Config {
+            scheme: Some("analyst".to_string()),
+            ..config
+        };
+        assert_eq!(names(&config, None)[0], "fastballs");
+        assert_eq!(names(&config, Some("default")), builtin);
+    }
+
+    #[test]
+    fn schemes_default_replaces_the_builtin() {
+        let config = parse("[[schemes.default.categories]]\nname = \"all\"\npitches = [\"FF\"]\n");
+        assert_eq!(config.scheme(None).unwrap().category_names(), ["all", "other", "unclassified"]);
+    }
+
+    #[test]
+    fn unknown_and_invalid_schemes() {
+        let config = parse(ANALYST);
+        let err = config.scheme(Some("nerd")).unwrap_err().to_string();
+        assert_eq!(err, "unknown scheme 'nerd' (available: default, analyst)");
+
+        let config = parse(&ANALYST.replace("\"CU\"", "\"XX\""));
+        assert!(config.scheme(Some("analyst")).is_err());
+        // an invalid scheme is only an error when it is used
+        assert!(config.scheme(None).is_ok());
+    }
+}
@@ -0,0 +1,87 @@
+//! Error kinds callers may want to branch on.
+
+use std::fmt;
+use std::process::ExitCode;
+
+/// Failures a calling script may want to tell apart; see [`Error::exit_code`].
+#[derive(Debug)]
+pub enum Error {
+    /// The game exists but no pitch has been thrown yet.
+    GameNotStarted {
+        /// The game's id, when the feed has one.
+        game_pk: Option<u64>,
+        /// Detailed state, e.g. "Scheduled".
+        state: String,
+    },
+    /// The game was postponed, cancelled or suspended.
+    GamePostponed {
+        /// The game's id, when the feed has one.
+        game_pk: Option<u64>,
+        /// Detailed state, e.g. "Postponed".
+        state: String,
+    },
+    /// No game matches the id, or the date/team lookup.
+    GameNotFound(String),
+    /// The feed does not have the expected shape.
+    Schema {
+        /// JSON path of the offending value, e.g. `liveData.plays.allPlays[3]`.
+        path: String,
+        /// What was wrong with it.
+        message: String,
+    },
+    /// The API could not be reached or kept failing.
+    Network(reqwest::Error),
+}
+
+impl Error {
+    /// Process exit code for this error. Clap exits with 2 on usage errors and
+    /// any other failure exits with 1.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Error::GameNotFound(_) => 3,
+            Error::GameNotStarted { .. } => 4,
+            Error::GamePostponed { .. } => 5,
+            Error::Schema { .. } => 6,
+            Error::Network(_) => 7,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let game = |pk: &Option<u64>| pk.map(|pk| format!("game {}", pk)).unwrap_or("game".to_string());
+        match self {
+            Error::GameNotStarted { game_pk, state } => {
+                write!(f, "{} has not started yet ({})", game(game_pk), state)
+            }
+            Error::GamePostponed { game_pk, state } => write!(f, "{} was not played ({})", game(game_pk), state),
+            Error::GameNotFound(what) => write!(f, "game not found: {}", what),
+            Error::Schema { path, message } => write!(f, "unexpected game feed schema at {}: {}", path, message),
+            Error::Network(_) => write!(f, "network request failed"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Network(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Error::Network(e)
+    }
+}
+
+/// Exit code for an error chain: the first [`Error`] found decides, otherwise 1.
+pub fn exit_code(err: &anyhow::Error) -> ExitCode {
+    let code = err
+        .chain()
+        .find_map(|e| e.downcast_ref::<Error>())
+        .map_or(1, Error::exit_code);
+    ExitCode::from(code)
+}
@@ -0,0 +1,92 @@
+//! Getting a game feed: from the API (through the cache) or from a saved file.
+
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use reqwest::header::{HeaderMap, HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::StatusCode;
+
+use crate::api::Api;
+use crate::cache::FeedCache;
+use crate::error::Error;
+use crate::model::{self, GameFeed};
+
+/// Fetch the `/feed/live` document of a game.
+///
+/// With a cache, final games are served from disk and in-progress games are
+/// revalidated; `refresh` skips the cached copy but still updates it.
+pub fn fetch_game_feed(
+    api: &Api,
+    game_pk: u64,
+    cache: Option<&FeedCache>,
+    refresh: bool,
+) -> Result<GameFeed> {
+    let cached = match cache {
+        Some(c) if !refresh => c.load(game_pk)?,
+        _ => None,
+    };
+    if let Some(c) = cached.as_ref().filter(|c| c.feed.is_final()) {
+        return Ok(c.feed.clone());
+    }
+
+    let url = api.url(&format!("/api/v1.1/game/{}/feed/live", game_pk));
+    let mut headers = HeaderMap::new();
+    // revalidate an in-progress game instead of downloading it again
+    if let Some(c) = &cached {
+        if let Some(etag) = c.etag.as_deref().and_then(|v| HeaderValue::from_str(v).ok()) {
+            headers.insert(IF_NONE_MATCH, etag);
+        }
+        if let Some(lm) = c.last_modified.as_deref().and_then(|v| HeaderValue::from_str(v).ok()) {
+            headers.insert(IF_MODIFIED_SINCE, lm);
+        }
+    }
+
+    let resp = api
+        .get(&url, headers)
+        .with_context(|| format!("fetching game {}", game_pk))?;
+    if resp.status() == StatusCode::NOT_MODIFIED {
+        if let Some(c) = cached {
+            return Ok(c.feed);
+        }
+    }
+    if resp.status() == StatusCode::NOT_FOUND {
+        return Err(Error::GameNotFound(format!("no game with id {}", game_pk)).into());
+    }
+    let resp = resp
+        .error_for_status()
+        .map_err(Error::from)
+        .with_context(|| format!("fetching game {}", game_pk))?;
+
+    let header = |name| {
+        resp.headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let etag = header(ETAG);
+    let last_modified = header(LAST_MODIFIED);
+    let text = resp
+        .text()
+        .map_err(Error::from)
+        .with_context(|| format!("fetching game {}", game_pk))?;
+    let feed = model::parse_feed(&text).with_context(|| format!("fetching game {}", game_pk))?;
+
+    if let Some(c) = cache {
+        c.store(game_pk, &text, etag.as_deref(), last_modified.as_deref())?;
+    }
+    Ok(feed)
+}
+
+/// Read a saved game feed from `path`, or from stdin when `path` is `-`.
+pub fn load_feed(path: &Path) -> Result<GameFeed> {
+    let text = if path == Path::new("-") {
+        let mut buf = String::new();
+        io::stdin().read_to_string(&mut buf)?;
+        buf
+    } else {
+        fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?
+    };
+    model::parse_feed(&text).with_context(|| format!("parsing game feed from {}", path.display()))
+}
@@ -0,0 +1,41 @@
+//! Pitch type summaries for MLB games, built on the public statsapi feeds.
+//!
+//! ```no_run
+//! use pitchers::api::{Api, HttpSettings};
+//! use pitchers::{fetch_game_feed, summarize_pitches, Scheme};
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let api = Api::new(&HttpSettings::default())?;
+//! let feed = fetch_game_feed(&api, 813026, None, false)?;
+//! for pitcher in summarize_pitches(&feed, &Scheme::default())?.pitchers {
+//!     println!("{} threw {} pitches", pitcher.name, pitcher.total());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod api;
+pub mod cache;
+pub mod config;
+pub mod error;
+pub mod feed;
+pub mod live;
+pub mod model;
+pub mod outcome;
+pub mod pitch;
+pub mod report;
+pub mod schedule;
+pub mod scheme;
+pub mod stats;
+pub mod summary;
+
+pub use error::Error;
+pub use feed::{fetch_game_feed, load_feed};
+pub use model::GameFeed;
+pub use pitch::{pitch_type, PitchType, PITCH_TYPES};
+pub use report::{print_json, print_summary, print_summary_since, summary_json, ReportOptions};
+pub use scheme::Scheme;
+pub use summary::{
+    summarize_pitches, CountBucket, GameSummary, InningSummary, PitchTypeSummary, PitcherSummary,
+    Side, Split, Summarizer,
+};
@@ -0,0 +1,347 @@
+//! Following a game in progress.
+//!
+//! The full feed is downloaded once. After that only the list of feed
+//! timecodes (`/feed/live/timestamps`) and the JSON patches between them
+//! (`/feed/live/diffPatch`) are fetched and applied to the feed in memory.
+
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use json_patch::PatchOperation;
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use crate::api::Api;
+use crate::error::Error;
+use crate::model::{self, GameFeed};
+use crate::scheme::Scheme;
+use crate::summary::{GameSummary, Summarizer};
+
+/// Polling interval when neither the caller nor the feed suggests one.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A game feed kept current with the API's JSON patches, and its summary.
+pub struct LiveGame<'a> {
+    api: &'a Api,
+    game_pk: u64,
+    /// The raw feed the patches apply to.
+    doc: Value,
+    feed: GameFeed,
+    timecode: Option<String>,
+    summarizer: Summarizer,
+    /// False while the game has not started.
+    started: bool,
+}
+
+impl<'a> LiveGame<'a> {
+    /// Download the full feed of a game; pitches are categorized by `scheme`.
+    pub fn start(api: &'a Api, game_pk: u64, scheme: Scheme) -> Result<Self> {
+        let mut game = LiveGame {
+            api,
+            game_pk,
+            doc: Value::Null,
+            feed: GameFeed::default(),
+            timecode: None,
+            summarizer: Summarizer::new(scheme),
+            started: false,
+        };
+        game.reload(None)?;
+        Ok(game)
+    }
+
+    /// The feed as of the latest refresh.
+    pub fn feed(&self) -> &GameFeed {
+        &self.feed
+    }
+
+    /// `None` while the game has not started.
+    pub fn summary(&self) -> Option<&GameSummary> {
+        self.started.then(|| self.summarizer.summary())
+    }
+
+    /// Catch up with the API. Returns whether the feed changed.
+    pub fn refresh(&mut self) -> Result<bool> {
+        let timecodes = self.get_json("/feed/live/timestamps", "")?;
+        let timecodes: Vec<String> = serde_json::from_value(timecodes)
+            .with_context(|| format!("reading timestamps of game {}", self.game_pk))?;
+        let Some(latest) = timecodes.last() else {
+            return Ok(false);
+        };
+        let Some(since) = self.timecode.clone() else {
+            self.reload(Some(latest.as_str()))?;
+            return Ok(true);
+        };
+        if *latest == since {
+            return Ok(false);
+        }
+
+        let query = format!("?startTimecode={}&endTimecode={}", since, latest);
+        let changes = match self.get_json("/feed/live/diffPatch", &query)? {
+            Value::Array(diffs) => match apply_diffs(&mut self.doc, diffs) {
+                Ok(paths) => Changes::from_paths(&paths, self.plays_read()),
+                // our copy no longer matches what the patches expect
+                Err(_) => return self.reload(Some(latest.as_str())).map(|_| true),
+            },
+            // the API sends the whole feed when that is smaller than the patches
+            full @ Value::Object(_) => {
+                self.doc = full;
+                Changes::everything()
+            }
+            _ => bail!("unexpected diffPatch response for game {}", self.game_pk),
+        };
+        self.update(&changes)?;
+        // the next patches start where these ended, whatever the feed's own timestamp says
+        self.timecode = Some(latest.clone());
+        Ok(true)
+    }
+
+    /// Download the full feed again. Its `metaData.timeStamp` is where the next
+    /// patches start, or `latest` when it has none.
+    fn reload(&mut self, latest: Option<&str>) -> Result<()> {
+        self.doc = self.get_json("/feed/live", "")?;
+        self.summarizer.reset();
+        self.update(&Changes::everything())?;
+        self.timecode = self.feed.meta_data.time_stamp.clone().or_else(|| latest.map(str::to_string));
+        Ok(())
+    }
+
+    fn plays_read(&self) -> usize {
+        self.feed.live_data.plays.all_plays.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Re-read what changed in `doc` into the typed feed and bring the summary up to date.
+    fn update(&mut self, changes: &Changes) -> Result<()> {
+        read_changes(&mut self.feed, &self.doc, changes)
+            .with_context(|| format!("reading game {}", self.game_pk))?;
+        let unchanged = changes.first_play.unwrap_or(usize::MAX);
+        self.started = match self.summarizer.update(&self.feed, unchanged) {
+            Ok(_) => true,
+            Err(e) if matches!(e.downcast_ref::<Error>(), Some(Error::GameNotStarted { .. })) => false,
+            Err(e) => return Err(e),
+        };
+        Ok(())
+    }
+
+    fn get_json(&self, endpoint: &str, query: &str) -> Result<Value> {
+        let url = self
+            .api
+            .url(&format!("/api/v1.1/game/{}{}{}", self.game_pk, endpoint, query));
+        let resp = self
+            .api
+            .get(&url, HeaderMap::new())
+            .with_context(|| format!("fetching game {}", self.game_pk))?;
+        if resp.status() == StatusCode::NOT_FOUND {
+            return Err(Error::GameNotFound(format!("no game with id {}", self.game_pk)).into());
+        }
+        resp.error_for_status()
+            .and_then(|r| r.json())
+            .map_err(Error::from)
+            .with_context(|| format!("fetching game {}", self.game_pk))
+    }
+}
+
+/// Apply the `diff` operations of each diffPatch entry, in order. Returns the
+/// JSON pointers of everything the operations changed.
+fn apply_diffs(doc: &mut Value, diffs: Vec<Value>) -> Result<Vec<String>> {
+    let mut paths = Vec::new();
+    for mut entry in diffs {
+        let diff = entry.get_mut("diff").map(Value::take).context("diffPatch entry without a diff")?;
+        let patch: json_patch::Patch = serde_json::from_value(diff)?;
+        json_patch::patch(doc, &patch)?;
+        for op in &patch.0 {
+            match op {
+                PatchOperation::Test(_) => {}
+                PatchOperation::Move(m) => paths.extend([m.from.to_string(), m.path.to_string()]),
+                op => paths.push(op.path().to_string()),
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// The parts of the feed a set of patches changed, as far as [`GameFeed`] reads it.
+#[derive(Debug, Default, PartialEq)]
+struct Changes {
+    /// The whole feed, or all of `liveData.plays`.
+    everything: bool,
+    /// `gamePk` or anything in `gameData`.
+    game_data: bool,
+    /// The lowest index of `liveData.plays.allPlays` that changed.
+    first_play: Option<usize>,
+}
+
+impl Changes {
+    fn everything() -> Self {
+        Changes {
+            everything: true,
+            game_data: true,
+            first_play: Some(0),
+        }
+    }
+
+    /// What the patched `paths` changed in a feed that had `plays` plays.
+    fn from_paths(paths: &[String], plays: usize) -> Self {
+        let mut changes = Changes::default();
+        for path in paths {
+            let mut segments = path.split('/').skip(1);
+            let play = match (segments.next(), segments.next(), segments.next(), segments.next()) {
+                (None, ..) | (Some("liveData"), None, ..) | (Some("liveData"), Some("plays"), None, _) => {
+                    return Changes::everything();
+                }
+                (Some("gamePk" | "gameData"), ..) => {
+                    changes.game_data = true;
+                    continue;
+                }
+                (Some("liveData"), Some("plays"), Some("allPlays"), index) => match index {
+                    None => 0,
+                    // appended
+                    Some("-") => plays,
+                    Some(i) => i.parse().unwrap_or(0),
+                },
+                // metaData is always re-read, the rest is not modelled
+                _ => continue,
+            };
+            changes.first_play = Some(changes.first_play.map_or(play, |p| p.min(play)));
+        }
+        changes
+    }
+}
+
+/// Bring `feed` up to date with `doc`, re-reading only the parts in `changes`.
+fn read_changes(feed: &mut GameFeed, doc: &Value, changes: &Changes) -> Result<()> {
+    const ALL_PLAYS: &str = "/liveData/plays/allPlays";
+    let plays = doc.pointer(ALL_PLAYS).and_then(Value::as_array);
+    if changes.everything || plays.is_none() {
+        *feed = model::feed_from_value(doc)?;
+        return Ok(());
+    }
+
+    feed.meta_data = model::part_from_value(doc, "/metaData")?;
+    if changes.game_data {
+        feed.game_pk = model::part_from_value(doc, "/gamePk")?;
+        feed.game_data = model::part_from_value(doc, "/gameData")?;
+    }
+    if let (Some(first), Some(plays)) = (changes.first_play, plays) {
+        let read = feed.live_data.plays.all_plays.get_or_insert_with(Vec::new);
+        read.truncate(first);
+        for i in read.len()..plays.len() {
+            read.push(model::part_from_value(doc, &format!("{}/{}", ALL_PLAYS, i))?);
+        }
+    }
+    Ok(())
+}
+
+/// Follow a game until it is final, or stops without finishing (suspended,
+/// postponed or cancelled; see [`GameFeed::halted_state`]).
+///
+/// `on_update` is called with the feed and its summary (`None` while the game
+/// has not started) once at the start and then whenever the feed changes. Polls
+/// are `interval` apart, but never closer together than the feed's
+/// `metaData.wait` hint.
+pub fn follow_game<F>(
+    api: &Api,
+    game_pk: u64,
+    scheme: Scheme,
+    interval: Option<Duration>,
+    mut on_update: F,
+) -> Result<()>
+where
+    F: FnMut(&GameFeed, Option<&GameSummary>) -> Result<()>,
+{
+    let mut game = LiveGame::start(api, game_pk, scheme)?;
+    on_update(game.feed(), game.summary())?;
+    while !game.feed().is_final() && game.feed().halted_state().is_none() {
+        thread::sleep(poll_interval(game.feed(), interval));
+        if game.refresh()? {
+            on_update(game.feed(), game.summary())?;
+        }
+    }
+    Ok(())
+}
+
+/// The requested interval (or the default), raised to the server's `wait` hint.
+pub fn poll_interval(feed: &GameFeed, requested: Option<Duration>) -> Duration {
+    let hint = feed.meta_data.wait.map(Duration::from_secs);
+    match (requested, hint) {
+        (Some(r), Some(h)) => r.max(h),
+        (Some(r), None) => r,
+        (None, Some(h)) => h,
+        (None, None) => DEFAULT_INTERVAL,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn apply_diffs_patches_in_order() {
+        let mut doc = json!({ "a": 1, "list": [] });
+        let diffs = vec![
+            json!({ "diff": [{ "op": "replace", "path": "/a", "value": 2 }] }),
+            json!({ "diff": [{ "op": "add", "path": "/list/-", "value": "x" }] }),
+        ];
+        apply_diffs(&mut doc, diffs).unwrap();
+        assert_eq!(doc, json!({ "a": 2, "list": ["x"] }));
+    }
+
+    #[test]
+    fn apply_diffs_rejects_malformed_entries() {
+        let mut doc = json!({ "a": 1 });
+        assert!(apply_diffs(&mut doc, vec![json!(["not", "an", "object"])]).is_err());
+        assert!(apply_diffs(&mut doc, vec![json!({ "no diff": [] })]).is_err());
+        assert!(apply_diffs(&mut doc, vec![json!({ "diff": [{ "op": "remove", "path": "/b" }] })]).is_err());
+    }
+
+    fn paths(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn changes_from_patch_paths() {
+        let changes = |p: &[&str]| Changes::from_paths(&paths(p), 5);
+        assert_eq!(changes(&["/metaData/timeStamp", "/liveData/linescore/currentInning"]), Changes::default());
+        assert_eq!(changes(&["/liveData/plays/allPlays/-"]).first_play, Some(5));
+        let revised = ["/liveData/plays/allPlays/-", "/liveData/plays/allPlays/4/playEvents/2/details/type/code"];
+        assert_eq!(changes(&revised).first_play, Some(4));
+        assert!(changes(&["/gameData/status/detailedState"]).game_data);
+        assert_eq!(changes(&["/liveData/plays"]), Changes::everything());
+        assert_eq!(changes(&[""]), Changes::everything());
+    }
+
+    #[test]
+    fn read_changes_matches_a_full_read() {
+        let play = |code: &str| {
+            let event = json!({ "isPitch": true, "details": { "code": "B", "type": { "code": code } } });
+            json!({ "matchup": { "pitcher": { "id": 1 } }, "playEvents": [event] })
+        };
+        let mut doc = json!({
+            "metaData": { "timeStamp": "1" },
+            "gameData": { "status": { "abstractGameState": "Live" } },
+            "liveData": { "plays": { "allPlays": [play("FF"), play("SL")] } },
+        });
+        let mut feed = model::feed_from_value(&doc).unwrap();
+
+        let diffs = vec![json!({ "diff": [
+            { "op": "replace", "path": "/metaData/timeStamp", "value": "2" },
+            { "op": "replace", "path": "/gameData/status/abstractGameState", "value": "Final" },
+            { "op": "replace", "path": "/liveData/plays/allPlays/1/playEvents/0/details/type/code",
+              "value": "ST" },
+            { "op": "add", "path": "/liveData/plays/allPlays/-", "value": play("CH") },
+        ] })];
+        let changes = Changes::from_paths(&apply_diffs(&mut doc, diffs).unwrap(), 2);
+        read_changes(&mut feed, &doc, &changes).unwrap();
+        let full = model::feed_from_value(&doc).unwrap();
+        assert_eq!(format!("{:?}", feed), format!("{:?}", full));
+
+        // plays before the first change are not read again
+        doc["liveData"]["plays"]["allPlays"][0] = play("CU");
+        let changes = Changes::from_paths(&paths(&["/liveData/plays/allPlays/2"]), 3);
+        read_changes(&mut feed, &doc, &changes).unwrap();
+        assert_eq!(format!("{:?}", feed), format!("{:?}", full));
+    }
+}
@@ -1,214 +1,370 @@
 // cargo run  -- --id 813026
+// cargo run  -- --date 2025-10-31 --team TOR
 
-use std::collections::HashMap;
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
 
-use anyhow::Result;
-use clap::Parser;
-use reqwest::blocking::Client;
-use serde_json::Value;
-use colored::Colorize;
+use anyhow::{bail, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use pitchers::api::{Api, HttpSettings};
+use pitchers::cache::FeedCache;
+use pitchers::config::Config;
+use pitchers::{
+    error, fetch_game_feed, live, load_feed, print_json, print_summary, print_summary_since,
+    schedule, summarize_pitches, GameSummary, ReportOptions, Scheme,
+};
 
 /// Summarize pitch types per pitcher for a single MLB game.
 #[derive(Parser)]
 struct Opts {
     /// Game id from MLB API. If provided, date/team args are ignored.
     #[arg(long)]
-    id: u64,
-}
+    id: Option<u64>,
 
-fn main() -> Result<()> {
-    let opts = Opts::parse();
-    let client = Client::builder()
-        .user_agent("pitchers-cli/0.1")
-        .build()?;
+    /// Game date (YYYY-MM-DD), used to look up the game id.
+    #[arg(long)]
+    date: Option<String>,
 
-    let game_id = opts.id;
+    /// Team abbreviation, name or id, used with --date to pick the game.
+    #[arg(long)]
+    team: Option<String>,
 
-    let feed = fetch_game_feed(&client, game_id)?;
-    let summary = summarize_pitches(&feed);
+    /// Which game of a doubleheader (1 or 2).
+    #[arg(long, requires = "date")]
+    game_number: Option<u64>,
 
-    print_summary(&summary);
+    /// Only show the pitcher with this MLBAM player id (repeatable).
+    #[arg(long, value_name = "PLAYER_ID")]
+    by_id: Vec<u64>,
 
-    Ok(())
+    /// Show release and plate velocity per pitch type.
+    #[arg(long)]
+    velo: bool,
+
+    /// Show spin rate and spin axis (clock-face tilt) per pitch type.
+    #[arg(long)]
+    spin: bool,
+
+    /// Show induced vertical and horizontal break per pitch type.
+    #[arg(long)]
+    movement: bool,
+
+    /// Show release height/side, extension and release point spread.
+    #[arg(long)]
+    release: bool,
+
+    /// Show balls, called/swinging strikes, fouls, balls in play and whiff/CSW/strike rates.
+    #[arg(long)]
+    outcomes: bool,
+
+    /// Show pitch type usage by ball-strike count.
+    #[arg(long)]
+    counts: bool,
+
+    /// Show pitch mix and outcomes vs left- and right-handed batters.
+    #[arg(long)]
+    platoon: bool,
+
+    /// Show pitches, cumulative pitch count and pitch mix per inning.
+    #[arg(long)]
+    innings: bool,
+
+    /// Order of categories: the scheme's, or most pitches first.
+    #[arg(long, value_enum, default_value_t = SortOrder::Scheme)]
+    sort: SortOrder,
+
+    /// Print the summary as JSON instead of text.
+    #[arg(long, conflicts_with = "live")]
+    json: bool,
+
+    /// Pitch category scheme from the config file (default: built-in).
+    #[arg(long, env = "PITCHERS_SCHEME", value_name = "NAME")]
+    scheme: Option<String>,
+
+    /// Keep polling an in-progress game and redraw the summary until it is final.
+    #[arg(long, conflicts_with = "feed_file")]
+    live: bool,
+
+    /// Seconds between polls in --live mode (never less than the API's hint).
+    #[arg(long, value_name = "SECS", requires = "live")]
+    interval: Option<u64>,
+
+    /// Read the game feed from a saved JSON file ("-" for stdin) instead of the API.
+    #[arg(long, visible_alias = "feed", value_name = "PATH")]
+    feed_file: Option<PathBuf>,
+
+    /// Directory for cached game feeds (default: $XDG_CACHE_HOME/pitchers).
+    #[arg(long, env = "PITCHERS_CACHE_DIR", global = true)]
+    cache_dir: Option<PathBuf>,
+
+    /// Re-download the game feed even if it is cached.
+    #[arg(long)]
+    refresh: bool,
+
+    /// Neither read from nor write to the feed cache.
+    #[arg(long, conflicts_with = "refresh")]
+    no_cache: bool,
+
+    /// Config file (default: $XDG_CONFIG_HOME/pitchers/config.toml).
+    #[arg(long, env = "PITCHERS_CONFIG", value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    #[command(flatten)]
+    http: HttpArgs,
+
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
-fn fetch_game_feed(client: &Client, game_pk: u64) -> Result<Value> {
-    let url = format!("https://statsapi.mlb.com/api/v1.1/game/{}/feed/live", game_pk);
-    let resp: Value = client.get(&url).send()?.error_for_status()?.json()?;
-    Ok(resp)
+/// HTTP client options; each one overrides the `[http]` table of the config file.
+#[derive(Args)]
+struct HttpArgs {
+    /// Base URL of the stats API, e.g. a local mock server or mirror.
+    #[arg(long, env = "PITCHERS_BASE_URL", value_name = "URL")]
+    base_url: Option<String>,
+
+    /// Request timeout in seconds (default: 30).
+    #[arg(long, env = "PITCHERS_TIMEOUT", value_name = "SECS")]
+    timeout: Option<u64>,
+
+    /// Connect timeout in seconds.
+    #[arg(long, env = "PITCHERS_CONNECT_TIMEOUT", value_name = "SECS")]
+    connect_timeout: Option<u64>,
+
+    /// Proxy URL for all requests.
+    #[arg(long, env = "PITCHERS_PROXY", value_name = "URL")]
+    proxy: Option<String>,
+
+    /// Extra PEM bundle of trusted CA certificates.
+    #[arg(long, env = "PITCHERS_CA_CERT", value_name = "PATH")]
+    ca_cert: Option<PathBuf>,
+
+    /// User-Agent header sent with every request.
+    #[arg(long, env = "PITCHERS_USER_AGENT")]
+    user_agent: Option<String>,
+
+    /// Retries for timeouts, 429 and 5xx responses (default: 3).
+    #[arg(long, env = "PITCHERS_RETRIES")]
+    retries: Option<u32>,
+
+    /// Initial retry backoff in milliseconds, doubled on every attempt (default: 500).
+    #[arg(long, env = "PITCHERS_BACKOFF_MS", value_name = "MS")]
+    backoff_ms: Option<u64>,
+
+    /// Maximum requests per second across the whole run.
+    #[arg(long, env = "PITCHERS_MAX_RPS", value_name = "N")]
+    max_rps: Option<f64>,
 }
 
-fn summarize_pitches(feed: &Value) -> HashMap<String, HashMap<String, HashMap<String, u32>>> {
-    let mut result: HashMap<String, HashMap<String, HashMap<String, u32>>> = HashMap::new();
-
-    let all_plays = feed
-        .get("liveData")
-        .and_then(|ld| ld.get("plays"))
-        .and_then(|p| p.get("allPlays"))
-        .and_then(|ap| ap.as_array())
-        .unwrap();
-
-    for play in all_plays {
-        let pitcher_name = play
-            .get("matchup")
-            .and_then(|m| m.get("pitcher"))
-            .and_then(|p| p.get("fullName"))
-            .and_then(|s| s.as_str())
-            .unwrap_or("Unknown pitcher")
-            .to_string();
-
-        if let Some(events) = play.get("playEvents").and_then(|e| e.as_array()) {
-            for ev in events {
-                if is_pitch_event(ev) {
-                    let raw_type = find_pitch_type(ev);
-                    let (pitch_name, pitch_category) = normalize_pitch_type(&raw_type);
-
-                    let pitcher_entry = result.entry(pitcher_name.clone()).or_insert_with(HashMap::new);
-                    let category_map = pitcher_entry
-                        .entry(pitch_category)
-                        .or_insert_with(HashMap::new);
-                    *category_map.entry(pitch_name).or_insert(0) += 1;
-                }
-            }
+impl From<&HttpArgs> for HttpSettings {
+    fn from(a: &HttpArgs) -> Self {
+        HttpSettings {
+            base_url: a.base_url.clone(),
+            timeout: a.timeout,
+            connect_timeout: a.connect_timeout,
+            proxy: a.proxy.clone(),
+            ca_cert: a.ca_cert.clone(),
+            user_agent: a.user_agent.clone(),
+            retries: a.retries,
+            backoff_ms: a.backoff_ms,
+            max_rps: a.max_rps,
         }
     }
+}
 
-    result
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SortOrder {
+    /// In the order the pitch category scheme lists them.
+    Scheme,
+    /// Most pitches first.
+    Usage,
 }
 
-fn is_pitch_event(ev: &Value) -> bool {
-    if let Some(b) = ev.get("isPitch").and_then(|v| v.as_bool()) {
-        return b;
-    }
-    ev.get("pitchData").is_some()
+#[derive(Subcommand)]
+enum Command {
+    /// Inspect or clean up the feed cache.
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
 }
 
-fn find_pitch_type(ev: &Value) -> String {
-    if let Some(details) = ev.get("details") {
-        if let Some(t) = details.get("type").and_then(|v| v.get("description")).and_then(|v| v.as_str()) {
-            return t.to_string().to_lowercase();
-        }
+#[derive(Subcommand)]
+enum CacheAction {
+    /// List cached games.
+    List,
+    /// Remove cached feeds of games that are not final.
+    Prune {
+        /// Remove every cached feed, final games included.
+        #[arg(long)]
+        all: bool,
+    },
+}
 
-        if let Some(desc) = details.get("description").and_then(|v| v.as_str()) {
-            return desc.to_string();
+fn main() -> ExitCode {
+    match run(Opts::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            error::exit_code(&e)
         }
     }
-
-    "unknown".to_string()
 }
 
-fn normalize_pitch_type(raw: &str) -> (String, String) {
-    let code = raw.trim();
-    if code.is_empty() {
-        return ("unknown".to_string(),  "unknown".to_string());
+fn run(opts: Opts) -> Result<()> {
+    if let Some(Command::Cache { action }) = &opts.command {
+        return run_cache_command(action, FeedCache::new(opts.cache_dir.clone())?);
     }
-    // common code-to-name mapping
-    let mappings: &[(&str, &str)] = &[
-        ("FF", "fastball"),
-        ("FA", "fastball"),
-        ("FT", "fastball"),
-        ("FF/FT", "fastball"),
-        ("SI", "sinker"),
-        ("SL", "slider"),
-        ("CU", "curveball"),
-        ("KC", "curveball"),
-        ("CH", "changeup"),
-        ("FC", "cutter"),
-        ("FS", "splitter"),
-        ("IN", "intentional"),
-    ];
-
-    // direct code match (uppercase)
-    let up = code.to_uppercase();
-    for (k, v) in mappings {
-        if up == *k {
-            return (v.to_string(), v.to_string());
+
+    let report = report_options(&opts);
+    let config = Config::load(opts.config.as_deref())?;
+    let scheme = config.scheme(opts.scheme.as_deref())?;
+
+    let feed = match &opts.feed_file {
+        Some(path) => load_feed(path)?,
+        None => {
+            let api = Api::new(&HttpSettings::from(&opts.http).or(config.http))?;
+            let game_id = resolve_game_id(&api, &opts)?;
+            if opts.live {
+                return run_live(&api, game_id, scheme, &opts, &report);
+            }
+            let cache = if opts.no_cache {
+                None
+            } else {
+                Some(FeedCache::new(opts.cache_dir.clone())?)
+            };
+            fetch_game_feed(&api, game_id, cache.as_ref(), opts.refresh)?
         }
-    }
+    };
 
-    // substring matching for common names
-    let low = code.to_lowercase();
-    if low.contains("fast")  {
-        return ("fastball".to_string(), "heater".to_string());
-    }
-    if low.contains("slider")  {
-        return ("slider".to_string(), "breaking ball".to_string());
-    }
-    if low.contains("curve")  {
-        return ("curveball".to_string(), "breaking ball".to_string());
-    }
-    if low.contains("change") {
-        return ("changeup".to_string(), "offspeed".to_string());
-    }
-    if low.contains("sinker")  {
-        return ("sinker".to_string(), "heater".to_string());
-    }
-    if low.contains("cutter")  {
-        return ("cutter".to_string(), "heater".to_string());
+    let mut summary = summarize_pitches(&feed, &scheme)?;
+    if !opts.by_id.is_empty() {
+        if let Some(missing) = opts.by_id.iter().find(|id| summary.pitcher(**id).is_none()) {
+            bail!("no pitcher with id {} pitched in this game", missing);
+        }
+        keep_selected_pitchers(&mut summary, &opts.by_id);
     }
-    if low.contains("splitter")  {
-        return ("splitter".to_string(), "offspeed".to_string());
+    if opts.json {
+        print_json(&summary, &report)?;
+    } else {
+        print_summary(&summary, &report);
     }
-    if low.contains("sweeper")  {
-        return ("sweeper".to_string(), "breaking ball".to_string());
+    warn_unclassified(&summary.unclassified);
+
+    Ok(())
+}
+
+fn warn_unclassified(labels: &[String]) {
+    if !labels.is_empty() {
+        eprintln!(
+            "warning: unrecognized pitch types, shown as unclassified: {}",
+            labels.join(", ")
+        );
     }
-    if low.contains("knuckle curve")  {
-        return ("knuckle curve".to_string(), "breaking ball".to_string());
+}
+
+fn report_options(opts: &Opts) -> ReportOptions {
+    ReportOptions {
+        velocity: opts.velo,
+        spin: opts.spin,
+        movement: opts.movement,
+        release: opts.release,
+        outcomes: opts.outcomes,
+        counts: opts.counts,
+        platoon: opts.platoon,
+        innings: opts.innings,
+        by_usage: opts.sort == SortOrder::Usage,
     }
-    if low.contains("knuckleball")  {
-        return ("knuckleball".to_string(), "other".to_string());
+}
+
+/// Apply --by-id; no-op when no ids were given.
+fn keep_selected_pitchers(summary: &mut GameSummary, ids: &[u64]) {
+    if !ids.is_empty() {
+        summary.pitchers.retain(|p| p.id.is_some_and(|id| ids.contains(&id)));
     }
-    // fallback to returning the raw label (helpful when API gives full text)
-    (code.to_string(), code.to_string())
 }
 
-fn print_summary(summary: &HashMap<String, HashMap<String, HashMap<String, u32>>>) {
-    println!("");
-    let mut names: Vec<_> = summary.keys().collect();
-    names.sort();
-    let preferred = ["heater", "breaking ball", "offspeed"];
-
-    for name in names {
-        let categories = &summary[name];
-
-        let total: u32 = categories.values().flat_map(|m| m.values()).sum();
-        // pad name first so ANSI escape sequences don't break alignment
-        let name_padded = format!("{:13}", name.bright_white().bold());
-        println!("{} ({})", &name_padded, total.to_string().bright_white().bold());
-
-        // print preferred categories first in that order
-        for cat in &preferred {
-            if let Some(pitches) = categories.get(*cat) {
-                let cat_total: u32 = pitches.values().sum();
-                println!("  {} {:>2}", cat.bright_yellow().bold(), cat_total);
-
-                let mut pairs: Vec<_> = pitches.iter().collect();
-                pairs.sort_by(|a, b| b.1.cmp(a.1));
-                for (ptype, count) in pairs {
-                    println!("    {:12} {:>3}", ptype, count);
-                }
+fn run_live(api: &Api, game_id: u64, scheme: Scheme, opts: &Opts, report: &ReportOptions) -> Result<()> {
+    let interval = opts.interval.map(Duration::from_secs);
+    let redraw = io::stdout().is_terminal();
+    let mut previous: Option<GameSummary> = None;
+    let mut warned = 0;
+
+    live::follow_game(api, game_id, scheme, interval, |feed, summary| {
+        if redraw {
+            // clear the screen and move the cursor home
+            print!("\x1b[2J\x1b[H");
+        }
+        let status = &feed.game_data.status;
+        let state = status.detailed_state.as_deref().unwrap_or("unknown state");
+        println!(
+            "game {}  {}  updated {}",
+            game_id,
+            state,
+            feed.meta_data.time_stamp.as_deref().unwrap_or("-")
+        );
+
+        match summary {
+            Some(summary) => {
+                warn_unclassified(&summary.unclassified[warned.min(summary.unclassified.len())..]);
+                warned = summary.unclassified.len();
+                let mut summary = summary.clone();
+                keep_selected_pitchers(&mut summary, &opts.by_id);
+                print_summary_since(&summary, previous.as_ref(), report);
+                previous = Some(summary);
             }
+            None => println!("waiting for the first pitch"),
         }
 
-        // then any other categories (sorted)
-        let mut other: Vec<_> = categories
-            .keys()
-            .filter(|k| !preferred.contains(&k.as_str()))
-            .collect();
-        other.sort();
-        for cat in other {
-            if let Some(pitches) = categories.get(cat) {
-                let cat_total: u32 = pitches.values().sum();
-                println!("  {} {:>2}", cat.bright_yellow().bold(), cat_total);
-
-                let mut pairs: Vec<_> = pitches.iter().collect();
-                pairs.sort_by(|a, b| b.1.cmp(a.1));
-                for (ptype, count) in pairs {
-                    println!("    {:12} {:>3}", ptype, count);
-                }
+        if feed.is_final() {
+            println!("game over");
+        } else if let Some(state) = feed.halted_state() {
+            println!("stopped following: the game is {}", state.to_lowercase());
+        } else {
+            println!(
+                "next refresh in {}s",
+                live::poll_interval(feed, interval).as_secs()
+            );
+        }
+        Ok(())
+    })
+}
+
+fn run_cache_command(action: &CacheAction, cache: FeedCache) -> Result<()> {
+    match action {
+        CacheAction::List => {
+            let entries = cache.list()?;
+            if entries.is_empty() {
+                println!("no cached games in {}", cache.dir().display());
             }
+            for e in entries {
+                println!(
+                    "{:>7}  {}  {:11}  {}  ({} KB)",
+                    e.game_pk,
+                    e.date,
+                    e.state,
+                    e.matchup,
+                    e.bytes / 1024
+                );
+            }
+        }
+        CacheAction::Prune { all } => {
+            let removed = cache.prune(*all)?;
+            println!("removed {} cached game(s) from {}", removed, cache.dir().display());
         }
+    }
+    Ok(())
+}
 
-        println!();
+fn resolve_game_id(api: &Api, opts: &Opts) -> Result<u64> {
+    match (opts.id, &opts.date) {
+        (Some(id), _) => Ok(id),
+        (None, Some(date)) => {
+            schedule::resolve_game_pk(api, date, opts.team.as_deref(), opts.game_number)
+        }
+        (None, None) => bail!("either --id, --date (with --team) or --feed-file is required"),
     }
 }
@@ -0,0 +1,398 @@
+//! Typed view of the statsapi `/feed/live` document.
+//!
+//! Only the parts this tool reads are modelled. Every field is optional or
+//! defaulted (explicit `null` included), so feeds for games in any state
+//! deserialize; a value of the wrong type is reported with its JSON path.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+use crate::error::Error;
+
+/// The statsapi `/feed/live` document of one game.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameFeed {
+    /// statsapi game id.
+    pub game_pk: Option<u64>,
+    /// Feed metadata.
+    #[serde(default, deserialize_with = "nullable")]
+    pub meta_data: MetaData,
+    /// Game facts: teams, players, status.
+    #[serde(default, deserialize_with = "nullable")]
+    pub game_data: GameData,
+    /// What happened: the plays.
+    #[serde(default, deserialize_with = "nullable")]
+    pub live_data: LiveData,
+}
+
+/// `metaData`: when the feed was updated and when to poll again.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetaData {
+    /// Suggested polling interval in seconds.
+    pub wait: Option<u64>,
+    /// Timecode of this version of the feed, e.g. "20251101_031500".
+    pub time_stamp: Option<String>,
+}
+
+/// `gameData`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameData {
+    /// Game identifiers.
+    #[serde(default, deserialize_with = "nullable")]
+    pub game: GameInfo,
+    /// Scheduling dates.
+    #[serde(default, deserialize_with = "nullable")]
+    pub datetime: GameDateTime,
+    /// Game state.
+    #[serde(default, deserialize_with = "nullable")]
+    pub status: GameStatus,
+    /// Away and home teams.
+    #[serde(default, deserialize_with = "nullable")]
+    pub teams: Teams,
+    /// Keyed by `"ID<playerId>"`.
+    #[serde(default, deserialize_with = "nullable")]
+    pub players: HashMap<String, Player>,
+}
+
+/// `gameData.game`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameInfo {
+    /// statsapi game id.
+    pub pk: Option<u64>,
+}
+
+/// `gameData.datetime`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameDateTime {
+    /// Date of the game in the home team's time zone, YYYY-MM-DD.
+    pub official_date: Option<String>,
+}
+
+/// `gameData.status`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameStatus {
+    /// "Preview", "Live" or "Final".
+    pub abstract_game_state: Option<String>,
+    /// e.g. "Scheduled", "In Progress", "Postponed", "Final".
+    pub detailed_state: Option<String>,
+}
+
+/// `gameData.teams`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Teams {
+    /// The visiting team.
+    #[serde(default, deserialize_with = "nullable")]
+    pub away: Team,
+    /// The home team.
+    #[serde(default, deserialize_with = "nullable")]
+    pub home: Team,
+}
+
+/// A team in `gameData.teams`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Team {
+    /// statsapi team id.
+    pub id: Option<u64>,
+    /// Full name, e.g. "Toronto Blue Jays".
+    pub name: Option<String>,
+    /// Abbreviation, e.g. "TOR".
+    pub abbreviation: Option<String>,
+    /// Club name, e.g. "Blue Jays".
+    pub team_name: Option<String>,
+}
+
+/// A player in `gameData.players`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Player {
+    /// MLBAM player id.
+    pub id: Option<u64>,
+    /// Full name.
+    pub full_name: Option<String>,
+    /// Throwing hand, code "L" or "R".
+    pub pitch_hand: Option<CodeDescription>,
+}
+
+/// A `{ code, description }` pair, used throughout the feed.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct CodeDescription {
+    /// Short code, e.g. "R" or "FF".
+    pub code: Option<String>,
+    /// Readable description.
+    pub description: Option<String>,
+}
+
+/// `liveData`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LiveData {
+    /// Every play of the game.
+    #[serde(default, deserialize_with = "nullable")]
+    pub plays: Plays,
+}
+
+/// `liveData.plays`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Plays {
+    /// Absent for games that have not started.
+    pub all_plays: Option<Vec<Play>>,
+}
+
+/// One plate appearance.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Play {
+    /// Where in the game it happened.
+    #[serde(default, deserialize_with = "nullable")]
+    pub about: About,
+    /// Pitcher, batter and their sides.
+    #[serde(default, deserialize_with = "nullable")]
+    pub matchup: Matchup,
+    /// Pitches and other events, in order.
+    #[serde(default, deserialize_with = "nullable")]
+    pub play_events: Vec<PlayEvent>,
+}
+
+/// `about` of a play.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct About {
+    /// Index of the play in `allPlays`.
+    pub at_bat_index: Option<u32>,
+    /// Inning number.
+    pub inning: Option<u32>,
+    /// "top" or "bottom".
+    pub half_inning: Option<String>,
+    /// Whether the away team is batting.
+    pub is_top_inning: Option<bool>,
+}
+
+/// `matchup` of a play.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Matchup {
+    /// The pitcher.
+    #[serde(default, deserialize_with = "nullable")]
+    pub pitcher: PersonRef,
+    /// The batter.
+    #[serde(default, deserialize_with = "nullable")]
+    pub batter: PersonRef,
+    /// Side the batter hits from, code "L" or "R".
+    pub bat_side: Option<CodeDescription>,
+    /// Throwing hand of the pitcher, code "L" or "R".
+    pub pitch_hand: Option<CodeDescription>,
+}
+
+/// A reference to a player.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonRef {
+    /// MLBAM player id.
+    pub id: Option<u64>,
+    /// Full name.
+    pub full_name: Option<String>,
+}
+
+/// A pitch, pickoff, mound visit or other event of a play.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayEvent {
+    /// Whether the event is a pitch.
+    pub is_pitch: Option<bool>,
+    /// "pitch", "action", "pickoff", ...
+    #[serde(rename = "type")]
+    pub event_type: Option<String>,
+    /// Pitch number within the plate appearance.
+    pub pitch_number: Option<u32>,
+    /// Call, description and pitch type.
+    #[serde(default, deserialize_with = "nullable")]
+    pub details: EventDetails,
+    /// Count after the event.
+    pub count: Option<Count>,
+    /// Tracking data of a pitch.
+    pub pitch_data: Option<PitchData>,
+    /// Batted ball data of a pitch put in play.
+    pub hit_data: Option<HitData>,
+}
+
+/// `details` of a play event.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventDetails {
+    /// The umpire's call.
+    pub call: Option<CodeDescription>,
+    /// Pitch result code, e.g. "B", "C", "S", "X".
+    pub code: Option<String>,
+    /// Readable result, e.g. "Called Strike".
+    pub description: Option<String>,
+    /// Pitch type, e.g. `{ "code": "FF", "description": "Four-Seam Fastball" }`.
+    #[serde(rename = "type")]
+    pub pitch_type: Option<CodeDescription>,
+    /// Whether the pitch counted as a strike.
+    pub is_strike: Option<bool>,
+    /// Whether the pitch counted as a ball.
+    pub is_ball: Option<bool>,
+    /// Whether the ball was put in play.
+    pub is_in_play: Option<bool>,
+}
+
+/// The ball-strike count and outs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+pub struct Count {
+    /// Balls.
+    #[serde(default)]
+    pub balls: u32,
+    /// Strikes.
+    #[serde(default)]
+    pub strikes: u32,
+    /// Outs.
+    #[serde(default)]
+    pub outs: u32,
+}
+
+/// `pitchData` of a pitch.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PitchData {
+    /// Release speed, mph.
+    pub start_speed: Option<f64>,
+    /// Speed at the plate, mph.
+    pub end_speed: Option<f64>,
+    /// Release extension, feet.
+    pub extension: Option<f64>,
+    /// Strike zone region, 1-9 inside the zone, 11-14 outside.
+    pub zone: Option<u32>,
+    /// Release point and movement.
+    #[serde(default, deserialize_with = "nullable")]
+    pub coordinates: Coordinates,
+    /// Spin and break.
+    #[serde(default, deserialize_with = "nullable")]
+    pub breaks: Breaks,
+}
+
+/// `pitchData.coordinates`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Coordinates {
+    /// Horizontal position at y = 50 ft, feet (catcher's view).
+    pub x0: Option<f64>,
+    /// Height at y = 50 ft, feet.
+    pub z0: Option<f64>,
+    /// Horizontal movement, inches (catcher's view).
+    pub pfx_x: Option<f64>,
+    /// Vertical movement without gravity, inches.
+    pub pfx_z: Option<f64>,
+}
+
+/// `pitchData.breaks`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Breaks {
+    /// rpm.
+    pub spin_rate: Option<f64>,
+    /// Spin axis, degrees.
+    pub spin_direction: Option<f64>,
+    /// Inches.
+    pub break_vertical_induced: Option<f64>,
+    /// Inches.
+    pub break_horizontal: Option<f64>,
+}
+
+/// `hitData` of a ball in play.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HitData {
+    /// Exit velocity, mph.
+    pub launch_speed: Option<f64>,
+    /// Launch angle, degrees.
+    pub launch_angle: Option<f64>,
+    /// Projected distance, feet.
+    pub total_distance: Option<f64>,
+    /// Batted ball type, e.g. "line_drive".
+    pub trajectory: Option<String>,
+    /// Contact quality, e.g. "hard".
+    pub hardness: Option<String>,
+}
+
+impl GameFeed {
+    /// Whether the game is over.
+    pub fn is_final(&self) -> bool {
+        self.game_data.status.abstract_game_state.as_deref() == Some("Final")
+    }
+
+    /// The detailed state of a game that stopped without finishing: suspended,
+    /// postponed or cancelled.
+    pub fn halted_state(&self) -> Option<&str> {
+        let state = self.game_data.status.detailed_state.as_deref()?;
+        let lower = state.to_lowercase();
+        ["suspended", "postponed", "cancelled"]
+            .iter()
+            .any(|s| lower.contains(s))
+            .then_some(state)
+    }
+}
+
+/// Parse a game feed, reporting the JSON path of any value with an unexpected type.
+pub fn parse_feed(text: &str) -> Result<GameFeed> {
+    let de = &mut serde_json::Deserializer::from_str(text);
+    serde_path_to_error::deserialize(de).map_err(|e| schema_error(e.path().to_string(), e.inner()))
+}
+
+/// Like [`parse_feed`], for a feed that is already held as JSON.
+pub fn feed_from_value(value: &Value) -> Result<GameFeed> {
+    serde_path_to_error::deserialize(value).map_err(|e| schema_error(e.path().to_string(), e.inner()))
+}
+
+/// Like [`feed_from_value`], for the part of a feed at JSON pointer `pointer`,
+/// e.g. `/liveData/plays/allPlays/3`. A missing or `null` part reads as the default.
+pub fn part_from_value<T: Default + DeserializeOwned>(feed: &Value, pointer: &str) -> Result<T> {
+    let value = match feed.pointer(pointer) {
+        None | Some(Value::Null) => return Ok(T::default()),
+        Some(v) => v,
+    };
+    serde_path_to_error::deserialize(value).map_err(|e| {
+        // liveData.plays.allPlays[3], then the path within the part
+        let mut path = String::new();
+        for segment in pointer.split('/').skip(1) {
+            match segment.parse::<usize>() {
+                Ok(i) => path.push_str(&format!("[{}]", i)),
+                Err(_) if path.is_empty() => path.push_str(segment),
+                Err(_) => path.push_str(&format!(".{}", segment)),
+            }
+        }
+        match e.path().to_string().as_str() {
+            "." => {}
+            inner if inner.starts_with('[') => path.push_str(inner),
+            inner => path.push_str(&format!(".{}", inner)),
+        }
+        schema_error(path, e.inner())
+    })
+}
+
+fn schema_error(path: String, err: &serde_json::Error) -> anyhow::Error {
+    Error::Schema {
+        path,
+        message: err.to_string(),
+    }
+    .into()
+}
+
+/// Deserialize `null` as the type's default value.
+fn nullable<'de, D, T>(de: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(de)?.unwrap_or_default())
+}
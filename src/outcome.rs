@@ -0,0 +1,183 @@
+//! What happened on each pitch: ball, called strike, whiff, foul or ball in play.
+
+use crate::model::EventDetails;
+
+/// Result of a single pitch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PitchOutcome {
+    /// Balls, including intentional and automatic balls and hit batters.
+    Ball,
+    /// A called strike.
+    CalledStrike,
+    /// Swinging strikes, including foul tips and missed bunts.
+    SwingingStrike,
+    /// A foul ball (not a foul tip).
+    Foul,
+    /// A ball put in play.
+    InPlay,
+}
+
+impl PitchOutcome {
+    /// Classify a pitch from its call code (`details.call.code` / `details.code`),
+    /// falling back to the `isBall`/`isInPlay` flags and the description.
+    pub fn from_details(details: &EventDetails) -> Option<PitchOutcome> {
+        let code = details
+            .call
+            .as_ref()
+            .and_then(|c| c.code.as_deref())
+            .or(details.code.as_deref());
+        let by_code = match code {
+            Some("B" | "*B" | "I" | "P" | "V" | "H") => Some(PitchOutcome::Ball),
+            Some("C") => Some(PitchOutcome::CalledStrike),
+            Some("S" | "W" | "T" | "M" | "Q") => Some(PitchOutcome::SwingingStrike),
+            Some("F" | "L" | "R" | "O") => Some(PitchOutcome::Foul),
+            Some("D" | "E" | "X") => Some(PitchOutcome::InPlay),
+            _ => None,
+        };
+        if by_code.is_some() {
+            return by_code;
+        }
+
+        if details.is_in_play == Some(true) {
+            return Some(PitchOutcome::InPlay);
+        }
+        if details.is_ball == Some(true) {
+            return Some(PitchOutcome::Ball);
+        }
+        let desc = details
+            .call
+            .as_ref()
+            .and_then(|c| c.description.as_deref())
+            .or(details.description.as_deref())?
+            .to_lowercase();
+        if desc.contains("swinging") || desc.contains("foul tip") || desc.contains("missed bunt") {
+            Some(PitchOutcome::SwingingStrike)
+        } else if desc.contains("foul") {
+            Some(PitchOutcome::Foul)
+        } else if desc.contains("called strike") {
+            Some(PitchOutcome::CalledStrike)
+        } else {
+            None
+        }
+    }
+}
+
+/// Pitch outcome counts and the rates derived from them.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Outcomes {
+    /// Balls.
+    pub balls: u32,
+    /// Called strikes.
+    pub called_strikes: u32,
+    /// Swinging strikes, foul tips and missed bunts.
+    pub swinging_strikes: u32,
+    /// Fouls.
+    pub fouls: u32,
+    /// Balls in play.
+    pub in_play: u32,
+}
+
+impl Outcomes {
+    /// Count one pitch.
+    pub fn add(&mut self, outcome: PitchOutcome) {
+        match outcome {
+            PitchOutcome::Ball => self.balls += 1,
+            PitchOutcome::CalledStrike => self.called_strikes += 1,
+            PitchOutcome::SwingingStrike => self.swinging_strikes += 1,
+            PitchOutcome::Foul => self.fouls += 1,
+            PitchOutcome::InPlay => self.in_play += 1,
+        }
+    }
+
+    /// Add another set of counts to this one.
+    pub fn merge(&mut self, other: &Outcomes) {
+        self.balls += other.balls;
+        self.called_strikes += other.called_strikes;
+        self.swinging_strikes += other.swinging_strikes;
+        self.fouls += other.fouls;
+        self.in_play += other.in_play;
+    }
+
+    /// Pitches with a known outcome.
+    pub fn total(&self) -> u32 {
+        self.balls + self.called_strikes + self.swinging_strikes + self.fouls + self.in_play
+    }
+
+    /// Swinging strikes, fouls and balls in play.
+    pub fn swings(&self) -> u32 {
+        self.swinging_strikes + self.fouls + self.in_play
+    }
+
+    /// Swinging strikes per swing.
+    pub fn whiff_rate(&self) -> Option<f64> {
+        ratio(self.swinging_strikes, self.swings())
+    }
+
+    /// Called plus swinging strikes per pitch.
+    pub fn csw_rate(&self) -> Option<f64> {
+        ratio(self.called_strikes + self.swinging_strikes, self.total())
+    }
+
+    /// Strikes (anything but a ball) per pitch.
+    pub fn strike_rate(&self) -> Option<f64> {
+        ratio(self.total() - self.balls, self.total())
+    }
+}
+
+fn ratio(part: u32, whole: u32) -> Option<f64> {
+    (whole > 0).then(|| part as f64 / whole as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::CodeDescription;
+
+    fn details(code: Option<&str>, description: Option<&str>) -> EventDetails {
+        EventDetails {
+            call: Some(CodeDescription {
+                code: code.map(str::to_string),
+                description: description.map(str::to_string),
+            }),
+            ..EventDetails::default()
+        }
+    }
+
+    #[test]
+    fn outcome_from_call_code() {
+        let outcome = |code| PitchOutcome::from_details(&details(Some(code), None));
+        assert_eq!(outcome("*B"), Some(PitchOutcome::Ball));
+        assert_eq!(outcome("C"), Some(PitchOutcome::CalledStrike));
+        assert_eq!(outcome("T"), Some(PitchOutcome::SwingingStrike));
+        assert_eq!(outcome("F"), Some(PitchOutcome::Foul));
+        assert_eq!(outcome("D"), Some(PitchOutcome::InPlay));
+    }
+
+    #[test]
+    fn outcome_without_a_known_code() {
+        let outcome = |description| PitchOutcome::from_details(&details(None, Some(description)));
+        assert_eq!(outcome("Foul Tip"), Some(PitchOutcome::SwingingStrike));
+        assert_eq!(outcome("Foul Bunt"), Some(PitchOutcome::Foul));
+        assert_eq!(outcome("Called Strike"), Some(PitchOutcome::CalledStrike));
+        assert_eq!(outcome("Automatic Strike"), None);
+
+        let in_play = EventDetails {
+            code: Some("?".to_string()),
+            is_in_play: Some(true),
+            ..EventDetails::default()
+        };
+        assert_eq!(PitchOutcome::from_details(&in_play), Some(PitchOutcome::InPlay));
+    }
+
+    #[test]
+    fn rates() {
+        let mut outcomes = Outcomes::default();
+        assert_eq!(outcomes.whiff_rate(), None);
+        for o in [PitchOutcome::Ball, PitchOutcome::CalledStrike, PitchOutcome::SwingingStrike, PitchOutcome::Foul] {
+            outcomes.add(o);
+        }
+        assert_eq!(outcomes.whiff_rate(), Some(0.5));
+        assert_eq!(outcomes.csw_rate(), Some(0.5));
+        assert_eq!(outcomes.strike_rate(), Some(0.75));
+    }
+}
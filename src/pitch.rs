@@ -0,0 +1,116 @@
+//! Pitch type normalization.
+
+/// A pitch type as statsapi codes it (`/api/v1/pitchTypes`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PitchType {
+    /// statsapi code, e.g. "FF".
+    pub code: &'static str,
+    /// Canonical lowercase name, e.g. "four-seam fastball".
+    pub name: &'static str,
+}
+
+const fn pitch(code: &'static str, name: &'static str) -> PitchType {
+    PitchType { code, name }
+}
+
+/// Every pitch type code statsapi uses, including the non-pitches it records as pitches.
+pub const PITCH_TYPES: &[PitchType] = &[
+    pitch("FF", "four-seam fastball"),
+    pitch("FA", "fastball"),
+    pitch("FT", "two-seam fastball"),
+    pitch("SI", "sinker"),
+    pitch("FC", "cutter"),
+    pitch("SL", "slider"),
+    pitch("ST", "sweeper"),
+    pitch("SV", "slurve"),
+    pitch("CU", "curveball"),
+    pitch("KC", "knuckle curve"),
+    pitch("CS", "slow curve"),
+    pitch("GY", "gyroball"),
+    pitch("CH", "changeup"),
+    pitch("FS", "splitter"),
+    pitch("FO", "forkball"),
+    pitch("SC", "screwball"),
+    pitch("KN", "knuckleball"),
+    pitch("EP", "eephus"),
+    pitch("PO", "pitchout"),
+    pitch("IN", "intentional ball"),
+    pitch("AB", "automatic ball"),
+    pitch("AS", "automatic strike"),
+    pitch("NP", "no pitch"),
+    pitch("UN", "unknown"),
+];
+
+/// Description fragments for labels that match no canonical name, most specific first.
+const DESCRIPTION_HINTS: &[(&str, &str)] = &[
+    ("knuckle curve", "KC"),
+    ("knuckle", "KN"),
+    ("slow curve", "CS"),
+    ("curve", "CU"),
+    ("sweep", "ST"),
+    ("slurve", "SV"),
+    ("slider", "SL"),
+    ("cut", "FC"),
+    ("sink", "SI"),
+    ("two-seam", "FT"),
+    ("four-seam", "FF"),
+    ("split", "FS"),
+    ("fork", "FO"),
+    ("change", "CH"),
+    ("screw", "SC"),
+    ("fast", "FA"),
+];
+
+/// Look up a statsapi pitch type code.
+pub fn pitch_type(code: &str) -> Option<&'static PitchType> {
+    let code = code.trim();
+    PITCH_TYPES.iter().find(|t| t.code.eq_ignore_ascii_case(code))
+}
+
+/// Find the pitch type a description such as "Four-Seam Fastball" names.
+pub fn pitch_type_for_description(description: &str) -> Option<&'static PitchType> {
+    let key = squash(description);
+    if let Some(t) = PITCH_TYPES.iter().find(|t| squash(t.name) == key) {
+        return Some(t);
+    }
+    let low = description.to_lowercase();
+    DESCRIPTION_HINTS
+        .iter()
+        .find(|(hint, _)| low.contains(hint))
+        .and_then(|(_, code)| pitch_type(code))
+}
+
+/// Identify a raw pitch type label: a statsapi code ("SL") or, for pitches
+/// without one, a description.
+pub fn identify(label: &str) -> Option<&'static PitchType> {
+    pitch_type(label).or_else(|| pitch_type_for_description(label))
+}
+
+/// Lowercase alphanumerics only, so "Knuckle Ball" matches "knuckleball".
+fn squash(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(label: &str) -> Option<&'static str> {
+        identify(label).map(|t| t.code)
+    }
+
+    #[test]
+    fn identify_codes_and_descriptions() {
+        assert_eq!(code("ff"), Some("FF"));
+        assert_eq!(code(" SL "), Some("SL"));
+        assert_eq!(code("Four-Seam Fastball"), Some("FF"));
+        assert_eq!(code("Knuckle Ball"), Some("KN"));
+        assert_eq!(code("Knuckle Curve"), Some("KC"));
+        assert_eq!(code("Split-Finger"), Some("FS"));
+        assert_eq!(code("Vulcan"), None);
+        assert_eq!(code(""), None);
+    }
+}
@@ -0,0 +1,539 @@
+//! Terminal and JSON output.
+
+use std::cmp::Reverse;
+
+use colored::Colorize;
+use serde_json::{json, Value};
+
+use crate::outcome::Outcomes;
+use crate::stats::{self, Stats};
+use crate::summary::{CountBucket, GameSummary, PitchTypeSummary, PitcherSummary, Side, Split};
+
+/// Which optional columns [`print_summary`] shows.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReportOptions {
+    /// Release and plate velocity per pitch type.
+    pub velocity: bool,
+    /// Spin rate and spin axis per pitch type.
+    pub spin: bool,
+    /// Induced vertical and arm-side horizontal break per pitch type.
+    pub movement: bool,
+    /// Release height, side and extension per pitch type, and release spread per pitcher.
+    pub release: bool,
+    /// Pitch outcomes with whiff, CSW and strike rates per pitch type, pitcher and team.
+    pub outcomes: bool,
+    /// Pitch type usage by ball-strike count under each pitcher.
+    pub counts: bool,
+    /// Pitch mix and outcomes vs left- and right-handed batters under each pitcher.
+    pub platoon: bool,
+    /// Pitches, cumulative pitch count and pitch mix per inning under each pitcher.
+    pub innings: bool,
+    /// Order categories by pitch count instead of the scheme's order.
+    pub by_usage: bool,
+}
+
+/// Print a summary to stdout: the away staff, then the home staff, each
+/// pitcher in order of appearance.
+pub fn print_summary(summary: &GameSummary, opts: &ReportOptions) {
+    print_summary_since(summary, None, opts);
+}
+
+/// Like [`print_summary`], highlighting the pitches added since `previous`.
+pub fn print_summary_since(summary: &GameSummary, previous: Option<&GameSummary>, opts: &ReportOptions) {
+    let empty = PitcherSummary::default();
+    // what `previous` showed for a pitcher; a new pitcher starts from nothing
+    let baseline = |p: &PitcherSummary| previous.map(|prev| prev.same_pitcher(p).unwrap_or(&empty));
+
+    println!();
+    for side in [Side::Away, Side::Home] {
+        let staff = summary.staff(side);
+        if staff.is_empty() {
+            continue;
+        }
+        print_team_header(summary, side, opts);
+        for pitcher in staff {
+            print_pitcher(pitcher, &summary.categories, baseline(pitcher), opts);
+        }
+    }
+
+    // pitchers the feed could not place on either team
+    for pitcher in summary.pitchers.iter().filter(|p| p.side.is_none()) {
+        print_pitcher(pitcher, &summary.categories, baseline(pitcher), opts);
+    }
+}
+
+fn print_team_header(summary: &GameSummary, side: Side, opts: &ReportOptions) {
+    let label = match side {
+        Side::Away => "away",
+        Side::Home => "home",
+    };
+    let name = summary.team_name(side).unwrap_or("Unknown team");
+    let total: u32 = summary.staff(side).iter().map(|p| p.total()).sum();
+    println!(
+        "{} ({}) {}",
+        name.bright_cyan().bold(),
+        label,
+        total.to_string().bright_cyan().bold()
+    );
+
+    let totals: Vec<String> = team_categories(summary, side, opts)
+        .into_iter()
+        .map(|cat| {
+            let count = summary.team_category_total(side, cat);
+            format!("{} {} {}", cat.bright_yellow(), count, usage(count, total).dimmed())
+        })
+        .collect();
+    println!("  {}", totals.join("  "));
+    if opts.outcomes {
+        println!("  {}", fmt_outcomes(&summary.team_outcomes(side)));
+    }
+    println!();
+}
+
+fn print_pitcher(
+    pitcher: &PitcherSummary,
+    order: &[String],
+    baseline: Option<&PitcherSummary>,
+    opts: &ReportOptions,
+) {
+    // pad name first so ANSI escape sequences don't break alignment
+    let name_padded = format!("{:13}", pitcher.name.bright_white().bold());
+    let id = pitcher.id.map(|id| format!(" [{}]", id)).unwrap_or_default();
+    let added = fmt_added(pitcher.total(), baseline.map(|b| b.total()));
+    println!(
+        "{}{} ({}){}",
+        &name_padded,
+        id.dimmed(),
+        pitcher.total().to_string().bright_white().bold(),
+        added
+    );
+    if opts.outcomes {
+        println!("  {}", fmt_outcomes(&pitcher.outcomes()));
+    }
+    if opts.release {
+        if let Some(spread) = pitcher.release_spread() {
+            println!("  release spread {:.1}\" across pitch types", spread);
+        }
+    }
+
+    let categories = pitcher_categories(pitcher, order, opts);
+    for cat in &categories {
+        print_category(pitcher, cat, baseline, opts);
+    }
+    if opts.counts {
+        print_count_matrix(pitcher, &categories);
+    }
+    if opts.platoon {
+        print_platoon(pitcher, &categories);
+    }
+    if opts.innings {
+        print_innings(pitcher);
+    }
+
+    println!();
+}
+
+const BUCKETS: [(CountBucket, &str); 3] = [
+    (CountBucket::Ahead, "ahead"),
+    (CountBucket::Even, "even"),
+    (CountBucket::Behind, "behind"),
+];
+
+/// Usage of each pitch type in every count, as a share of the pitches thrown in that count.
+fn print_count_matrix(pitcher: &PitcherSummary, categories: &[&str]) {
+    let counts: Vec<(u32, u32)> = (0..4).flat_map(|b| (0..3).map(move |s| (b, s))).collect();
+
+    let mut header = format!("  {:18}", "count".dimmed());
+    for (b, s) in &counts {
+        header.push_str(&format!(" {:>4}", format!("{}-{}", b, s)));
+    }
+    for (_, label) in BUCKETS {
+        header.push_str(&format!(" {:>6}", label));
+    }
+    println!("{}", header);
+
+    let mut totals = format!("  {:18}", "pitches");
+    for (b, s) in &counts {
+        totals.push_str(&format!(" {:>4}", pitcher.at_count(*b, *s)));
+    }
+    for (bucket, _) in BUCKETS {
+        totals.push_str(&format!(" {:>6}", pitcher.in_bucket(bucket)));
+    }
+    println!("{}", totals);
+
+    for cat in categories {
+        for ptype in pitcher.pitch_types_in(cat) {
+            let mut row = format!("  {:18}", ptype.name);
+            for (b, s) in &counts {
+                let share = usage(ptype.at_count(*b, *s), pitcher.at_count(*b, *s));
+                row.push_str(&format!(" {:>4}", share));
+            }
+            for (bucket, _) in BUCKETS {
+                let share = usage(ptype.in_bucket(bucket), pitcher.in_bucket(bucket));
+                row.push_str(&format!(" {:>6}", share));
+            }
+            println!("{}", row);
+        }
+    }
+    println!();
+}
+
+/// Pitch mix and outcomes against left- and right-handed batters, side by side.
+fn print_platoon(pitcher: &PitcherSummary, categories: &[&str]) {
+    let (left, right) = (pitcher.vs_left(), pitcher.vs_right());
+    println!("  {:18} {:<29}   vs RHB", "platoon".dimmed(), "vs LHB");
+    println!(
+        "  {:18} {}   {}",
+        "pitches",
+        fmt_split(&left, left.count),
+        fmt_split(&right, right.count)
+    );
+    for cat in categories {
+        for ptype in pitcher.pitch_types_in(cat) {
+            println!(
+                "  {:18} {}   {}",
+                ptype.name,
+                fmt_split(&ptype.vs_left, left.count),
+                fmt_split(&ptype.vs_right, right.count)
+            );
+        }
+    }
+    println!();
+}
+
+/// One line per inning: pitches, running pitch count and pitch mix.
+fn print_innings(pitcher: &PitcherSummary) {
+    println!("  {:8} {:>7} {:>5}  mix", "inning".dimmed(), "pitches", "total");
+    for (inning, total) in pitcher.innings.iter().zip(pitcher.cumulative_pitches()) {
+        let mut mix = inning.pitch_types.clone();
+        mix.sort_by_key(|(_, n)| Reverse(*n));
+        let mix: Vec<String> = mix.iter().map(|(name, n)| format!("{} {}", name, n)).collect();
+        println!("  {:<8} {:>7} {:>5}  {}", inning.inning, inning.pitches, total, mix.join(", "));
+    }
+    println!();
+}
+
+/// `12  40%  whiff 25% CSW 33%`
+fn fmt_split(split: &Split, total: u32) -> String {
+    format!(
+        "{:>3} {:>4}  whiff {} CSW {}",
+        split.count,
+        usage(split.count, total),
+        fmt_pct(split.outcomes.whiff_rate()),
+        fmt_pct(split.outcomes.csw_rate())
+    )
+}
+
+fn usage(part: u32, whole: u32) -> String {
+    if whole == 0 {
+        "-".to_string()
+    } else {
+        format!("{:.0}%", part as f64 * 100.0 / whole as f64)
+    }
+}
+
+/// Categories in the scheme's `order`, then any others (sorted).
+fn ordered_categories<'a>(categories: Vec<&'a str>, order: &'a [String]) -> Vec<&'a str> {
+    let mut other: Vec<_> = categories.iter().copied().filter(|c| !order.iter().any(|o| o == c)).collect();
+    other.sort();
+    order
+        .iter()
+        .map(String::as_str)
+        .filter(|c| categories.contains(c))
+        .chain(other)
+        .collect()
+}
+
+/// A pitcher's categories in display order.
+fn pitcher_categories<'a>(pitcher: &'a PitcherSummary, order: &'a [String], opts: &ReportOptions) -> Vec<&'a str> {
+    let mut categories = ordered_categories(pitcher.categories(), order);
+    if opts.by_usage {
+        categories.sort_by_key(|c| Reverse(pitcher.category_total(c)));
+    }
+    categories
+}
+
+/// A team's categories in display order.
+fn team_categories<'a>(summary: &'a GameSummary, side: Side, opts: &ReportOptions) -> Vec<&'a str> {
+    let mut categories = ordered_categories(summary.team_categories(side), &summary.categories);
+    if opts.by_usage {
+        categories.sort_by_key(|c| Reverse(summary.team_category_total(side, c)));
+    }
+    categories
+}
+
+fn print_category(pitcher: &PitcherSummary, cat: &str, baseline: Option<&PitcherSummary>, opts: &ReportOptions) {
+    let cat_total = pitcher.category_total(cat);
+    println!(
+        "  {} {:>2} {:>4}",
+        cat.bright_yellow().bold(),
+        cat_total,
+        usage(cat_total, pitcher.total())
+    );
+    for ptype in pitcher.pitch_types_in(cat) {
+        let before = baseline.map(|b| b.pitch_type(&ptype.name, &ptype.category).map_or(0, |t| t.count));
+        println!(
+            "    {:18} {:>3} {:>4} ({:>4}){}{}",
+            ptype.name,
+            ptype.count,
+            usage(ptype.count, pitcher.total()),
+            usage(ptype.count, cat_total),
+            extra_columns(ptype, opts),
+            fmt_added(ptype.count, before)
+        );
+    }
+}
+
+/// ` +3`, highlighted, when the count grew since the previous refresh.
+fn fmt_added(now: u32, before: Option<u32>) -> String {
+    match before {
+        Some(b) if now > b => format!(" {}", format!("+{}", now - b).bright_green().bold()),
+        _ => String::new(),
+    }
+}
+
+fn extra_columns(ptype: &PitchTypeSummary, opts: &ReportOptions) -> String {
+    let mut cols = String::new();
+    if opts.velocity {
+        cols.push_str(&format!(
+            "  {} mph {}  end {}",
+            fmt_mean(&ptype.start_speed),
+            fmt_range(&ptype.start_speed),
+            fmt_mean(&ptype.end_speed)
+        ));
+    }
+    if opts.spin {
+        let axis = &ptype.spin_axis;
+        cols.push_str(&format!(
+            "  {:>4} rpm ±{:<3}  tilt {:>5} ±{}°",
+            fmt_whole(ptype.spin_rate.mean()),
+            fmt_whole(ptype.spin_rate.std_dev()),
+            axis.mean().map_or("-".to_string(), stats::clock_tilt),
+            fmt_whole(axis.std_dev())
+        ));
+    }
+    if opts.release {
+        cols.push_str(&format!(
+            "  rel {}/{} ft  ext {} ft",
+            fmt_feet(ptype.release_height.mean()),
+            fmt_feet(ptype.release_side.mean()),
+            fmt_feet(ptype.extension.mean())
+        ));
+    }
+    if opts.outcomes {
+        cols.push_str("  ");
+        cols.push_str(&fmt_outcomes(&ptype.outcomes));
+    }
+    if opts.movement {
+        cols.push_str(&format!(
+            "  IVB {}\"  HB {}\"",
+            fmt_signed(ptype.induced_vertical_break.mean()),
+            fmt_signed(ptype.horizontal_break.mean())
+        ));
+    }
+    cols
+}
+
+fn fmt_mean(stats: &Stats) -> String {
+    match stats.mean() {
+        Some(m) => format!("{:>5.1}", m),
+        None => format!("{:>5}", "-"),
+    }
+}
+
+fn fmt_signed(value: Option<f64>) -> String {
+    value.map_or(format!("{:>5}", "-"), |v| format!("{:>+5.1}", v))
+}
+
+/// `B 12 CS 5 SS 4 F 6 BIP 3  whiff 31% CSW 30% str 60%`
+fn fmt_outcomes(o: &Outcomes) -> String {
+    format!(
+        "B {:>2} CS {:>2} SS {:>2} F {:>2} BIP {:>2}  whiff {} CSW {} str {}",
+        o.balls,
+        o.called_strikes,
+        o.swinging_strikes,
+        o.fouls,
+        o.in_play,
+        fmt_pct(o.whiff_rate()),
+        fmt_pct(o.csw_rate()),
+        fmt_pct(o.strike_rate())
+    )
+}
+
+fn fmt_pct(rate: Option<f64>) -> String {
+    rate.map_or(format!("{:>4}", "-"), |r| format!("{:>3.0}%", r * 100.0))
+}
+
+fn fmt_feet(value: Option<f64>) -> String {
+    value.map_or("-".to_string(), |v| format!("{:.2}", v))
+}
+
+fn fmt_whole(value: Option<f64>) -> String {
+    value.map_or("-".to_string(), |v| format!("{:.0}", v))
+}
+
+/// `[min-max ±sd]`
+fn fmt_range(stats: &Stats) -> String {
+    match (stats.min(), stats.max(), stats.std_dev()) {
+        (Some(lo), Some(hi), Some(sd)) => format!("[{:.1}-{:.1} ±{:.1}]", lo, hi, sd),
+        _ => "[-]".to_string(),
+    }
+}
+
+/// Print a summary to stdout as JSON: pitch counts and usage percentages per
+/// pitcher, category and pitch type, in the same order as the text output.
+pub fn print_json(summary: &GameSummary, opts: &ReportOptions) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(&summary_json(summary, opts))?);
+    Ok(())
+}
+
+/// The JSON document [`print_json`] prints.
+pub fn summary_json(summary: &GameSummary, opts: &ReportOptions) -> Value {
+    let pitchers: Vec<Value> = [Some(Side::Away), Some(Side::Home), None]
+        .into_iter()
+        .flat_map(|side| summary.pitchers.iter().filter(move |p| p.side == side))
+        .map(|p| pitcher_json(p, &summary.categories, opts))
+        .collect();
+
+    json!({
+        "gamePk": summary.game_pk,
+        "awayTeam": summary.away_team,
+        "homeTeam": summary.home_team,
+        "pitchers": pitchers,
+        "unclassified": summary.unclassified,
+    })
+}
+
+fn pitcher_json(pitcher: &PitcherSummary, order: &[String], opts: &ReportOptions) -> Value {
+    let total = pitcher.total();
+    let categories: Vec<Value> = pitcher_categories(pitcher, order, opts)
+        .into_iter()
+        .map(|cat| {
+            let cat_total = pitcher.category_total(cat);
+            let pitch_types: Vec<Value> = pitcher
+                .pitch_types_in(cat)
+                .into_iter()
+                .map(|t| {
+                    json!({
+                        "name": t.name,
+                        "pitches": t.count,
+                        "percent": percent(t.count, total),
+                        "categoryPercent": percent(t.count, cat_total),
+                    })
+                })
+                .collect();
+            json!({
+                "name": cat,
+                "pitches": cat_total,
+                "percent": percent(cat_total, total),
+                "pitchTypes": pitch_types,
+            })
+        })
+        .collect();
+
+    json!({
+        "id": pitcher.id,
+        "name": pitcher.name,
+        "team": pitcher.team,
+        "side": pitcher.side.map(|s| match s {
+            Side::Away => "away",
+            Side::Home => "home",
+        }),
+        "hand": pitcher.hand,
+        "pitches": total,
+        "categories": categories,
+    })
+}
+
+/// `part` as a percentage of `whole`, to one decimal; `None` when `whole` is 0.
+fn percent(part: u32, whole: u32) -> Option<f64> {
+    (whole > 0).then(|| (part as f64 * 1000.0 / whole as f64).round() / 10.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::model::feed_from_value;
+    use crate::scheme::Scheme;
+    use crate::summary::summarize_pitches;
+
+    fn summary() -> GameSummary {
+        let play = |half: &str, id: u64, codes: &[&str]| {
+            let events: Vec<Value> = codes
+                .iter()
+                .map(|code| json!({ "isPitch": true, "details": { "code": "B", "type": { "code": code } } }))
+                .collect();
+            json!({
+                "about": { "inning": 1, "halfInning": half },
+                "matchup": { "pitcher": { "id": id, "fullName": format!("Pitcher {}", id) } },
+                "playEvents": events,
+            })
+        };
+        let feed = feed_from_value(&json!({
+            "gamePk": 1,
+            "gameData": {
+                "status": { "abstractGameState": "Final" },
+                "teams": { "away": { "name": "Away" }, "home": { "name": "Home" } },
+            },
+            "liveData": { "plays": { "allPlays": [
+                play("top", 2, &["FF", "SL", "ST"]),
+                play("bottom", 1, &["FF"]),
+                play("bottom", 1, &["Vulcan"]),
+            ] } },
+        }))
+        .unwrap();
+        summarize_pitches(&feed, &Scheme::default()).unwrap()
+    }
+
+    #[test]
+    fn percents_round_to_one_decimal() {
+        assert_eq!(percent(1, 3), Some(33.3));
+        assert_eq!(percent(2, 3), Some(66.7));
+        assert_eq!(percent(3, 3), Some(100.0));
+        assert_eq!(percent(0, 0), None);
+    }
+
+    #[test]
+    fn summary_json_lists_the_away_staff_first() {
+        let doc = summary_json(&summary(), &ReportOptions::default());
+        assert_eq!(doc["gamePk"], json!(1));
+        assert_eq!((&doc["awayTeam"], &doc["homeTeam"]), (&json!("Away"), &json!("Home")));
+        assert_eq!(doc["unclassified"], json!(["VULCAN"]));
+
+        let pitchers = doc["pitchers"].as_array().unwrap();
+        assert_eq!(pitchers.len(), 2);
+        assert_eq!((&pitchers[0]["id"], &pitchers[0]["side"]), (&json!(1), &json!("away")));
+        assert_eq!((&pitchers[1]["id"], &pitchers[1]["side"]), (&json!(2), &json!("home")));
+
+        let home = &pitchers[1];
+        assert_eq!(home["pitches"], json!(3));
+        assert_eq!(
+            home["categories"],
+            json!([
+                { "name": "heater", "pitches": 1, "percent": 33.3, "pitchTypes": [
+                    { "name": "four-seam fastball", "pitches": 1, "percent": 33.3, "categoryPercent": 100.0 },
+                ] },
+                { "name": "breaking ball", "pitches": 2, "percent": 66.7, "pitchTypes": [
+                    { "name": "slider", "pitches": 1, "percent": 33.3, "categoryPercent": 50.0 },
+                    { "name": "sweeper", "pitches": 1, "percent": 33.3, "categoryPercent": 50.0 },
+                ] },
+            ])
+        );
+    }
+
+    #[test]
+    fn summary_json_follows_the_sort_order() {
+        let names = |opts: &ReportOptions| -> Vec<Value> {
+            let doc = summary_json(&summary(), opts);
+            doc["pitchers"][1]["categories"].as_array().unwrap().iter().map(|c| c["name"].clone()).collect()
+        };
+        assert_eq!(names(&ReportOptions::default()), [json!("heater"), json!("breaking ball")]);
+        let by_usage = ReportOptions {
+            by_usage: true,
+            ..ReportOptions::default()
+        };
+        assert_eq!(names(&by_usage), [json!("breaking ball"), json!("heater")]);
+    }
+}
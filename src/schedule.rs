@@ -0,0 +1,233 @@
+//! Finding a gamePk by date and team through the schedule endpoint.
+
+use anyhow::{bail, Context, Result};
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+
+use crate::api::Api;
+use crate::error::Error;
+
+/// A scheduled game as listed by the statsapi schedule endpoint.
+struct ScheduledGame {
+    game_pk: u64,
+    game_number: u64,
+    state: String,
+    away: Value,
+    home: Value,
+}
+
+impl ScheduledGame {
+    fn describe(&self) -> String {
+        let name = |t: &Value| {
+            t.get("name")
+                .and_then(|v| v.as_str())
+                .unwrap_or("Unknown team")
+                .to_string()
+        };
+        format!(
+            "{}  game {}  {} @ {}  ({})",
+            self.game_pk,
+            self.game_number,
+            name(&self.away),
+            name(&self.home),
+            self.state
+        )
+    }
+}
+
+/// Resolve a gamePk from a date and (optionally) a team.
+///
+/// `team` may be an abbreviation ("TOR"), a team id ("141") or any part of
+/// the team name ("blue jays"). `game_number` picks a game of a doubleheader.
+pub fn resolve_game_pk(
+    api: &Api,
+    date: &str,
+    team: Option<&str>,
+    game_number: Option<u64>,
+) -> Result<u64> {
+    validate_date(date)?;
+    let games = fetch_schedule(api, date).with_context(|| format!("fetching schedule for {}", date))?;
+
+    // an exact id/abbreviation/name match anywhere on the schedule beats a partial name
+    // match, so "LAD" does not also pick "Philadelphia"
+    let plays_in = |game: &ScheduledGame, matches: fn(&Value, &str) -> bool, t: &str| {
+        matches(&game.away, t) || matches(&game.home, t)
+    };
+    let exact = team.is_some_and(|t| games.iter().any(|g| plays_in(g, team_is, t)));
+
+    let mut candidates: Vec<&ScheduledGame> = Vec::new();
+    for game in &games {
+        let team_matches = match team {
+            Some(t) if exact => plays_in(game, team_is, t),
+            Some(t) => plays_in(game, team_name_contains, t),
+            None => true,
+        };
+        let number_matches = game_number.is_none_or(|n| game.game_number == n);
+        if team_matches && number_matches {
+            candidates.push(game);
+        }
+    }
+
+    match candidates.len() {
+        0 => Err(Error::GameNotFound(format!(
+            "no game on {}{}",
+            date,
+            team.map(|t| format!(" for team '{}'", t)).unwrap_or_default()
+        ))
+        .into()),
+        1 => Ok(candidates[0].game_pk),
+        _ => {
+            let mut msg = format!("{} games match; pass --id or narrow the search:", candidates.len());
+            for g in candidates {
+                msg.push_str("\n  ");
+                msg.push_str(&g.describe());
+            }
+            if game_number.is_none() {
+                msg.push_str("\nFor doubleheaders, use --game-number 1 or 2.");
+            }
+            bail!(msg)
+        }
+    }
+}
+
+fn fetch_schedule(api: &Api, date: &str) -> Result<Vec<ScheduledGame>> {
+    let url = api.url(&format!("/api/v1/schedule?sportId=1&date={}&hydrate=team", date));
+    let resp: Value = api
+        .get(&url, HeaderMap::new())?
+        .error_for_status()
+        .and_then(|r| r.json())
+        .map_err(Error::from)?;
+
+    let mut games = Vec::new();
+    let dates = resp.get("dates").and_then(|d| d.as_array());
+    for day in dates.into_iter().flatten() {
+        let day_games = day.get("games").and_then(|g| g.as_array());
+        for g in day_games.into_iter().flatten() {
+            let Some(game_pk) = g.get("gamePk").and_then(|v| v.as_u64()) else {
+                continue;
+            };
+            let team = |side: &str| {
+                g.get("teams")
+                    .and_then(|t| t.get(side))
+                    .and_then(|t| t.get("team"))
+                    .cloned()
+                    .unwrap_or(Value::Null)
+            };
+
+            games.push(ScheduledGame {
+                game_pk,
+                game_number: g.get("gameNumber").and_then(|v| v.as_u64()).unwrap_or(1),
+                state: g
+                    .get("status")
+                    .and_then(|s| s.get("detailedState"))
+                    .and_then(|s| s.as_str())
+                    .unwrap_or("Unknown")
+                    .to_string(),
+                away: team("away"),
+                home: team("home"),
+            });
+        }
+    }
+
+    Ok(games)
+}
+
+const NAME_FIELDS: [&str; 5] = ["name", "teamName", "shortName", "clubName", "locationName"];
+
+/// Whether `query` is the team's id, abbreviation or one of its names.
+fn team_is(team: &Value, query: &str) -> bool {
+    let query = query.trim();
+    if let Ok(id) = query.parse::<u64>() {
+        return team.get("id").and_then(|v| v.as_u64()) == Some(id);
+    }
+
+    let str_field = |key: &str| team.get(key).and_then(|v| v.as_str()).unwrap_or("");
+    str_field("abbreviation").eq_ignore_ascii_case(query)
+        || NAME_FIELDS.iter().any(|k| str_field(k).eq_ignore_ascii_case(query))
+}
+
+/// Whether any of the team's names contains `query`.
+fn team_name_contains(team: &Value, query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() || query.parse::<u64>().is_ok() {
+        return false;
+    }
+    NAME_FIELDS
+        .iter()
+        .filter_map(|k| team.get(*k).and_then(|v| v.as_str()))
+        .any(|name| name.to_lowercase().contains(&query))
+}
+
+fn validate_date(date: &str) -> Result<()> {
+    let parts: Vec<&str> = date.split('-').collect();
+    let well_formed = parts.len() == 3
+        && parts[0].len() == 4
+        && parts[1].len() == 2
+        && parts[2].len() == 2
+        && parts.iter().all(|p| p.chars().all(|c| c.is_ascii_digit()));
+    let valid = well_formed && {
+        let (year, month, day): (u32, u32, u32) =
+            (parts[0].parse()?, parts[1].parse()?, parts[2].parse()?);
+        (1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day)
+    };
+    if !valid {
+        bail!("invalid date '{}', expected YYYY-MM-DD", date);
+    }
+    Ok(())
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn dodgers() -> Value {
+        json!({ "id": 119, "name": "Los Angeles Dodgers", "abbreviation": "LAD", "teamName": "Dodgers" })
+    }
+
+    fn phillies() -> Value {
+        json!({ "id": 143, "name": "Philadelphia Phillies", "abbreviation": "PHI", "teamName": "Phillies" })
+    }
+
+    #[test]
+    fn exact_team_matches() {
+        assert!(team_is(&dodgers(), "LAD"));
+        assert!(team_is(&dodgers(), "lad"));
+        assert!(team_is(&dodgers(), "119"));
+        assert!(team_is(&dodgers(), "dodgers"));
+        assert!(!team_is(&phillies(), "LAD"));
+        assert!(!team_is(&phillies(), "119"));
+    }
+
+    #[test]
+    fn partial_team_names() {
+        assert!(team_name_contains(&phillies(), "phil"));
+        assert!(team_name_contains(&phillies(), "LAD"));
+        assert!(!team_name_contains(&dodgers(), "11"));
+        assert!(!team_name_contains(&dodgers(), " "));
+    }
+
+    #[test]
+    fn dates() {
+        assert!(validate_date("2025-10-31").is_ok());
+        assert!(validate_date("2024-02-29").is_ok());
+        assert!(validate_date("2000-02-29").is_ok());
+        assert!(validate_date("1900-02-29").is_err());
+        assert!(validate_date("2025-02-29").is_err());
+        assert!(validate_date("2025-13-45").is_err());
+        assert!(validate_date("2025-04-31").is_err());
+        assert!(validate_date("2025-00-10").is_err());
+        assert!(validate_date("2025-1-5").is_err());
+        assert!(validate_date("10/31/2025").is_err());
+    }
+}
@@ -0,0 +1,160 @@
+//! Pitch category schemes: how pitch types are grouped, and in which order
+//! the groups are shown.
+
+use anyhow::{bail, Result};
+use serde::Deserialize;
+
+use crate::pitch::{self, PitchType, PITCH_TYPES};
+
+/// Category for pitch types a scheme does not list.
+pub const OTHER: &str = "other";
+
+/// Category for pitches whose type label is not a known pitch type.
+pub const UNCLASSIFIED: &str = "unclassified";
+
+/// A named grouping of pitch types into categories.
+///
+/// In `config.toml`:
+///
+/// ```toml
+/// [[schemes.analyst.categories]]
+/// name = "fastballs"
+/// pitches = ["FF", "FA", "FT", "SI"]
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scheme {
+    /// In display order.
+    pub categories: Vec<Category>,
+}
+
+/// One category of a [`Scheme`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Category {
+    /// Shown as the category heading.
+    pub name: String,
+    /// statsapi codes ("SL") or canonical pitch names ("slider").
+    pub pitches: Vec<String>,
+}
+
+impl Default for Scheme {
+    /// The built-in scheme: heater, breaking ball, offspeed and other.
+    fn default() -> Self {
+        let category = |name: &str, codes: &[&str]| Category {
+            name: name.to_string(),
+            pitches: codes.iter().map(|c| c.to_string()).collect(),
+        };
+        Scheme {
+            categories: vec![
+                category("heater", &["FF", "FA", "FT", "SI", "FC"]),
+                category("breaking ball", &["SL", "ST", "SV", "CU", "KC", "CS", "GY"]),
+                category("offspeed", &["CH", "FS", "FO", "SC"]),
+                category(OTHER, &["KN", "EP", "PO", "IN", "AB", "AS", "NP", "UN"]),
+            ],
+        }
+    }
+}
+
+impl Scheme {
+    /// Category names in display order, ending with [`OTHER`] and [`UNCLASSIFIED`].
+    /// Every pitch is classified into one of these.
+    pub fn category_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.categories.iter().map(|c| c.name.clone()).collect();
+        if !names.iter().any(|n| n == OTHER) {
+            names.push(OTHER.to_string());
+        }
+        names.push(UNCLASSIFIED.to_string());
+        names
+    }
+
+    /// The category a pitch type belongs to.
+    pub fn category_of(&self, pitch: &PitchType) -> &str {
+        self.categories
+            .iter()
+            .find(|c| c.pitches.iter().any(|p| names(p, pitch)))
+            .map_or(OTHER, |c| c.name.as_str())
+    }
+
+    /// Map a raw pitch type label to a `(pitch name, category)` pair.
+    ///
+    /// Known pitch types get their canonical name; anything else keeps its raw
+    /// label and lands in [`UNCLASSIFIED`].
+    pub fn classify(&self, raw: &str) -> (String, String) {
+        match pitch::identify(raw) {
+            Some(t) => (t.name.to_string(), self.category_of(t).to_string()),
+            None => (raw.trim().to_string(), UNCLASSIFIED.to_string()),
+        }
+    }
+
+    /// Check that category names are unique and every listed pitch is a known
+    /// pitch type, listed once.
+    pub fn validate(&self, name: &str) -> Result<()> {
+        let mut seen: Vec<&PitchType> = Vec::new();
+        for (i, category) in self.categories.iter().enumerate() {
+            if category.name == UNCLASSIFIED {
+                bail!("scheme '{}': category name '{}' is reserved", name, UNCLASSIFIED);
+            }
+            if self.categories[..i].iter().any(|c| c.name == category.name) {
+                bail!("scheme '{}': category '{}' is defined more than once", name, category.name);
+            }
+            for p in &category.pitches {
+                let Some(t) = PITCH_TYPES.iter().find(|t| names(p, t)) else {
+                    bail!("scheme '{}': unknown pitch type '{}'", name, p);
+                };
+                if seen.contains(&t) {
+                    bail!("scheme '{}': pitch type '{}' is in more than one category", name, p);
+                }
+                seen.push(t);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether a scheme entry (code or canonical name) names `pitch`.
+fn names(entry: &str, pitch: &PitchType) -> bool {
+    let entry = entry.trim();
+    entry.eq_ignore_ascii_case(pitch.code) || entry.eq_ignore_ascii_case(pitch.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scheme(categories: &[(&str, &[&str])]) -> Scheme {
+        Scheme {
+            categories: categories
+                .iter()
+                .map(|(name, pitches)| Category {
+                    name: name.to_string(),
+                    pitches: pitches.iter().map(|p| p.to_string()).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn classify_pitch_labels() {
+        let default = Scheme::default();
+        assert_eq!(default.classify("ST"), ("sweeper".to_string(), "breaking ball".to_string()));
+        assert_eq!(default.classify("splitter"), ("splitter".to_string(), "offspeed".to_string()));
+        assert_eq!(default.classify("PO"), ("pitchout".to_string(), OTHER.to_string()));
+        assert_eq!(default.classify(" Vulcan "), ("Vulcan".to_string(), UNCLASSIFIED.to_string()));
+
+        let fastballs = scheme(&[("fastballs", &["FF", "sinker"])]);
+        assert_eq!(fastballs.classify("SI").1, "fastballs");
+        assert_eq!(fastballs.classify("SL").1, OTHER);
+        assert_eq!(fastballs.category_names(), ["fastballs", OTHER, UNCLASSIFIED]);
+    }
+
+    #[test]
+    fn validate_schemes() {
+        assert!(Scheme::default().validate("default").is_ok());
+        assert!(scheme(&[("hard", &["FF", "four-seam fastball"])]).validate("s").is_err());
+        assert!(scheme(&[("hard", &["FF"]), ("soft", &["ff"])]).validate("s").is_err());
+        assert!(scheme(&[("hard", &["FF"]), ("hard", &["SL"])]).validate("s").is_err());
+        assert!(scheme(&[("hard", &["gyro slider"])]).validate("s").is_err());
+        assert!(scheme(&[(UNCLASSIFIED, &["FF"])]).validate("s").is_err());
+    }
+}
@@ -0,0 +1,173 @@
+//! Running statistics over pitch measurements.
+
+/// Count, mean, min, max and standard deviation of a series of values,
+/// accumulated one value at a time (Welford's algorithm).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Stats {
+    n: u32,
+    mean: f64,
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Stats {
+    /// Add a value.
+    pub fn add(&mut self, x: f64) {
+        if self.n == 0 {
+            self.min = x;
+            self.max = x;
+        } else {
+            self.min = self.min.min(x);
+            self.max = self.max.max(x);
+        }
+        self.n += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.n as f64;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    /// Add `x` if present.
+    pub fn add_opt(&mut self, x: Option<f64>) {
+        if let Some(x) = x {
+            self.add(x);
+        }
+    }
+
+    /// Number of values added.
+    pub fn count(&self) -> u32 {
+        self.n
+    }
+
+    /// Mean, or `None` without values.
+    pub fn mean(&self) -> Option<f64> {
+        (self.n > 0).then_some(self.mean)
+    }
+
+    /// Smallest value.
+    pub fn min(&self) -> Option<f64> {
+        (self.n > 0).then_some(self.min)
+    }
+
+    /// Largest value.
+    pub fn max(&self) -> Option<f64> {
+        (self.n > 0).then_some(self.max)
+    }
+
+    /// Population standard deviation.
+    pub fn std_dev(&self) -> Option<f64> {
+        (self.n > 0).then(|| (self.m2 / self.n as f64).sqrt())
+    }
+}
+
+/// Mean and spread of angles in degrees, e.g. spin axes, where 359° and 1°
+/// are neighbours rather than opposites.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AngleStats {
+    n: u32,
+    sin_sum: f64,
+    cos_sum: f64,
+}
+
+impl AngleStats {
+    /// Add an angle.
+    pub fn add(&mut self, degrees: f64) {
+        let rad = degrees.to_radians();
+        self.n += 1;
+        self.sin_sum += rad.sin();
+        self.cos_sum += rad.cos();
+    }
+
+    /// Add `degrees` if present.
+    pub fn add_opt(&mut self, degrees: Option<f64>) {
+        if let Some(d) = degrees {
+            self.add(d);
+        }
+    }
+
+    /// Number of angles added.
+    pub fn count(&self) -> u32 {
+        self.n
+    }
+
+    /// Circular mean in `[0, 360)` degrees.
+    pub fn mean(&self) -> Option<f64> {
+        (self.n > 0).then(|| self.sin_sum.atan2(self.cos_sum).to_degrees().rem_euclid(360.0))
+    }
+
+    /// Circular standard deviation in degrees.
+    pub fn std_dev(&self) -> Option<f64> {
+        if self.n == 0 {
+            return None;
+        }
+        let r = (self.sin_sum.hypot(self.cos_sum) / self.n as f64).min(1.0);
+        // ln(r) <= 0; abs() also turns the -0.0 of r == 1 into 0.0
+        Some((-2.0 * r.ln()).abs().sqrt().to_degrees())
+    }
+}
+
+/// A spin axis in degrees as clock-face tilt ("1:15"), where 180° is 12:00
+/// (pure backspin) and each hour is 30°, rounded to the nearest 15 minutes.
+pub fn clock_tilt(axis_degrees: f64) -> String {
+    let minutes = ((axis_degrees + 180.0).rem_euclid(360.0) * 2.0 / 15.0).round() as u32 * 15;
+    let minutes = minutes % 720;
+    let hour = match minutes / 60 {
+        0 => 12,
+        h => h,
+    };
+    format!("{}:{:02}", hour, minutes % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn stats_of_a_series() {
+        let mut stats = Stats::default();
+        assert_eq!((stats.mean(), stats.std_dev()), (None, None));
+        for x in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+            stats.add(x);
+        }
+        stats.add_opt(None);
+        assert_eq!(stats.count(), 8);
+        assert_eq!(stats.mean(), Some(5.0));
+        assert_eq!((stats.min(), stats.max()), (Some(2.0), Some(9.0)));
+        assert!(close(stats.std_dev().unwrap(), 2.0));
+    }
+
+    #[test]
+    fn angles_wrap_around() {
+        let mut angles = AngleStats::default();
+        angles.add(350.0);
+        angles.add(10.0);
+        assert!(close(angles.mean().unwrap(), 0.0) || close(angles.mean().unwrap(), 360.0));
+        assert!(angles.std_dev().unwrap() < 10.5);
+    }
+
+    #[test]
+    fn identical_angles_have_no_spread() {
+        let mut angles = AngleStats::default();
+        assert_eq!(angles.std_dev(), None);
+        angles.add(210.0);
+        angles.add(210.0);
+        let spread = angles.std_dev().unwrap();
+        assert_eq!(spread, 0.0);
+        assert!(spread.is_sign_positive());
+    }
+
+    #[test]
+    fn clock_tilt_of_spin_axes() {
+        assert_eq!(clock_tilt(180.0), "12:00");
+        assert_eq!(clock_tilt(210.0), "1:00");
+        assert_eq!(clock_tilt(217.0), "1:15");
+        assert_eq!(clock_tilt(90.0), "9:00");
+        assert_eq!(clock_tilt(0.0), "6:00");
+        assert_eq!(clock_tilt(178.0), "12:00");
+        assert_eq!(clock_tilt(-150.0), "1:00");
+    }
+}
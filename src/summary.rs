@@ -0,0 +1,831 @@
+//! Per-pitcher pitch type summaries.
+
+use std::cmp::Reverse;
+
+use anyhow::Result;
+
+use crate::error::Error;
+use crate::model::{Count, GameFeed, Play, PlayEvent};
+use crate::outcome::{Outcomes, PitchOutcome};
+use crate::scheme::{Scheme, UNCLASSIFIED};
+use crate::stats::{AngleStats, Stats};
+
+/// Every pitcher's pitches in one game.
+#[derive(Debug, Clone, Default)]
+pub struct GameSummary {
+    /// statsapi game id.
+    pub game_pk: Option<u64>,
+    /// Away team name.
+    pub away_team: Option<String>,
+    /// Home team name.
+    pub home_team: Option<String>,
+    /// Pitch categories of the scheme used, in display order.
+    pub categories: Vec<String>,
+    /// In order of appearance.
+    pub pitchers: Vec<PitcherSummary>,
+    /// Raw pitch type labels that matched no known pitch type.
+    pub unclassified: Vec<String>,
+}
+
+/// Which team a pitcher pitched for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    /// The visiting team.
+    Away,
+    /// The home team.
+    Home,
+}
+
+/// One pitcher's pitches, by pitch type.
+#[derive(Debug, Clone, Default)]
+pub struct PitcherSummary {
+    /// MLBAM player id.
+    pub id: Option<u64>,
+    /// Full name.
+    pub name: String,
+    /// Name of the team the pitcher pitched for.
+    pub team: Option<String>,
+    /// Away or home, when known.
+    pub side: Option<Side>,
+    /// Throwing hand, "L" or "R".
+    pub hand: Option<String>,
+    /// 1 for the first pitcher to appear in the game, 2 for the next, ...
+    pub appearance: usize,
+    /// In order of first use.
+    pub pitch_types: Vec<PitchTypeSummary>,
+    /// Innings pitched, in order.
+    pub innings: Vec<InningSummary>,
+}
+
+/// A pitcher's pitches in one half-inning.
+#[derive(Debug, Clone, Default)]
+pub struct InningSummary {
+    /// Inning number.
+    pub inning: u32,
+    /// "top" or "bottom".
+    pub half: String,
+    /// Pitches thrown.
+    pub pitches: u32,
+    /// Pitch name and count, in order of first use.
+    pub pitch_types: Vec<(String, u32)>,
+}
+
+impl InningSummary {
+    fn add(&mut self, pitch_name: &str) {
+        self.pitches += 1;
+        match self.pitch_types.iter_mut().find(|(name, _)| name == pitch_name) {
+            Some((_, n)) => *n += 1,
+            None => self.pitch_types.push((pitch_name.to_string(), 1)),
+        }
+    }
+}
+
+/// Statistics for one pitch type thrown by one pitcher.
+#[derive(Debug, Clone, Default)]
+pub struct PitchTypeSummary {
+    /// Normalized pitch name, e.g. "slider".
+    pub name: String,
+    /// Pitch category, e.g. "breaking ball".
+    pub category: String,
+    /// Pitches thrown.
+    pub count: u32,
+    /// Release speed, mph.
+    pub start_speed: Stats,
+    /// Speed at the plate, mph.
+    pub end_speed: Stats,
+    /// rpm.
+    pub spin_rate: Stats,
+    /// Spin axis, degrees.
+    pub spin_axis: AngleStats,
+    /// Horizontal movement in inches, positive toward the pitcher's arm side.
+    pub horizontal_break: Stats,
+    /// Vertical movement without gravity in inches, positive up.
+    pub induced_vertical_break: Stats,
+    /// Release height (`z0`), feet.
+    pub release_height: Stats,
+    /// Release side (`x0`), feet from the center of the rubber, catcher's view.
+    pub release_side: Stats,
+    /// Release extension toward the plate, feet.
+    pub extension: Stats,
+    /// Pitch results.
+    pub outcomes: Outcomes,
+    /// Pitches by the count they were thrown in, indexed `[balls][strikes]`.
+    pub by_count: [[u32; 3]; 4],
+    /// Pitches to left-handed batters.
+    pub vs_left: Split,
+    /// Pitches to right-handed batters.
+    pub vs_right: Split,
+}
+
+/// Pitches and their outcomes against batters hitting from one side.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Split {
+    /// Pitches thrown.
+    pub count: u32,
+    /// Pitch results.
+    pub outcomes: Outcomes,
+}
+
+impl Split {
+    fn add(&mut self, outcome: Option<PitchOutcome>) {
+        self.count += 1;
+        if let Some(o) = outcome {
+            self.outcomes.add(o);
+        }
+    }
+
+    fn merge(&mut self, other: &Split) {
+        self.count += other.count;
+        self.outcomes.merge(&other.outcomes);
+    }
+}
+
+/// Counts grouped by who is ahead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CountBucket {
+    /// More strikes than balls: 0-1, 0-2, 1-2.
+    Ahead,
+    /// 0-0, 1-1, 2-2 and the full count.
+    Even,
+    /// 1-0, 2-0, 3-0, 2-1, 3-1.
+    Behind,
+}
+
+impl CountBucket {
+    /// The `(balls, strikes)` counts in the bucket.
+    pub fn counts(self) -> &'static [(u32, u32)] {
+        match self {
+            CountBucket::Ahead => &[(0, 1), (0, 2), (1, 2)],
+            CountBucket::Even => &[(0, 0), (1, 1), (2, 2), (3, 2)],
+            CountBucket::Behind => &[(1, 0), (2, 0), (3, 0), (2, 1), (3, 1)],
+        }
+    }
+}
+
+/// What the summary knows about a pitch beyond the event itself.
+struct PitchContext<'a> {
+    /// Pitcher's throwing hand.
+    hand: Option<&'a str>,
+    /// Count before the pitch.
+    count: Count,
+    /// Side the batter hits from, "L" or "R".
+    bat_side: Option<&'a str>,
+}
+
+impl GameSummary {
+    /// Name of the away or home team.
+    pub fn team_name(&self, side: Side) -> Option<&str> {
+        match side {
+            Side::Away => self.away_team.as_deref(),
+            Side::Home => self.home_team.as_deref(),
+        }
+    }
+
+    /// A team's pitchers, in order of appearance.
+    pub fn staff(&self, side: Side) -> Vec<&PitcherSummary> {
+        self.pitchers.iter().filter(|p| p.side == Some(side)).collect()
+    }
+
+    /// Pitches a team threw in `category`.
+    pub fn team_category_total(&self, side: Side, category: &str) -> u32 {
+        self.staff(side).iter().map(|p| p.category_total(category)).sum()
+    }
+
+    /// Outcomes of all pitches a team threw.
+    pub fn team_outcomes(&self, side: Side) -> Outcomes {
+        let mut total = Outcomes::default();
+        for p in self.staff(side) {
+            total.merge(&p.outcomes());
+        }
+        total
+    }
+
+    /// Categories a team's pitchers used, in order of first use.
+    pub fn team_categories(&self, side: Side) -> Vec<&str> {
+        let mut cats: Vec<&str> = Vec::new();
+        for p in self.staff(side) {
+            for c in p.categories() {
+                if !cats.contains(&c) {
+                    cats.push(c);
+                }
+            }
+        }
+        cats
+    }
+
+    /// This summary's entry for a pitcher taken from another summary of the same game.
+    pub fn same_pitcher(&self, other: &PitcherSummary) -> Option<&PitcherSummary> {
+        match other.id {
+            Some(_) => self.pitchers.iter().find(|p| p.id == other.id),
+            None => self.pitchers.iter().find(|p| p.id.is_none() && p.name == other.name),
+        }
+    }
+
+    /// Pitches thrown in the game.
+    pub fn total_pitches(&self) -> u32 {
+        self.pitchers.iter().map(|p| p.total()).sum()
+    }
+
+    /// The pitcher with MLBAM player id `id`.
+    pub fn pitcher(&self, id: u64) -> Option<&PitcherSummary> {
+        self.pitchers.iter().find(|p| p.id == Some(id))
+    }
+
+    fn record_pitch(&mut self, scheme: &Scheme, feed: &GameFeed, play: &Play, ev: &PlayEvent, count: Count) {
+        let raw_type = find_pitch_type(ev);
+        let (pitch_name, pitch_category) = scheme.classify(&raw_type);
+        if pitch_category == UNCLASSIFIED && !self.unclassified.contains(&raw_type) {
+            self.unclassified.push(raw_type.clone());
+        }
+
+        let pitcher = self.pitcher_mut(feed, play);
+        let hand = pitcher.hand.clone();
+        let ctx = PitchContext {
+            hand: hand.as_deref(),
+            count,
+            bat_side: play.matchup.bat_side.as_ref().and_then(|b| b.code.as_deref()),
+        };
+        if let Some(inning) = play.about.inning {
+            let half = play.about.half_inning.as_deref().unwrap_or("");
+            pitcher.inning_mut(inning, half).add(&pitch_name);
+        }
+        pitcher
+            .pitch_type_mut(pitch_name, pitch_category)
+            .record(ev, &ctx);
+    }
+
+    /// Position of the play's pitcher in `pitchers`, if they have pitched already.
+    /// Pitchers are keyed by player id, or by name when the feed lacks an id.
+    fn pitcher_position(&self, feed: &GameFeed, play: &Play) -> Option<usize> {
+        match play.matchup.pitcher.id {
+            Some(id) => self.pitchers.iter().position(|p| p.id == Some(id)),
+            None => {
+                let name = pitcher_name(feed, play);
+                self.pitchers.iter().position(|p| p.id.is_none() && p.name == name)
+            }
+        }
+    }
+
+    /// The play's pitcher's entry, created on first sight.
+    fn pitcher_mut(&mut self, feed: &GameFeed, play: &Play) -> &mut PitcherSummary {
+        if let Some(i) = self.pitcher_position(feed, play) {
+            return &mut self.pitchers[i];
+        }
+
+        let id = play.matchup.pitcher.id;
+        let player = id.and_then(|id| feed.game_data.players.get(&format!("ID{}", id)));
+        let name = pitcher_name(feed, play);
+
+        // the home team pitches in the top of the inning
+        let side = match play.about.half_inning.as_deref() {
+            Some("top") => Some(Side::Home),
+            Some("bottom") => Some(Side::Away),
+            _ => play.about.is_top_inning.map(|top| if top { Side::Home } else { Side::Away }),
+        };
+        let team = side.and_then(|s| self.team_name(s).map(str::to_string));
+        let hand = play
+            .matchup
+            .pitch_hand
+            .as_ref()
+            .or_else(|| player.and_then(|p| p.pitch_hand.as_ref()))
+            .and_then(|h| h.code.clone());
+
+        self.pitchers.push(PitcherSummary {
+            id,
+            name,
+            team,
+            side,
+            hand,
+            appearance: self.pitchers.len() + 1,
+            pitch_types: Vec::new(),
+            innings: Vec::new(),
+        });
+        self.pitchers.last_mut().unwrap()
+    }
+}
+
+/// A pitcher's name from the game's players, else from the play.
+fn pitcher_name(feed: &GameFeed, play: &Play) -> String {
+    let id = play.matchup.pitcher.id;
+    id.and_then(|id| feed.game_data.players.get(&format!("ID{}", id)))
+        .and_then(|p| p.full_name.clone())
+        .or_else(|| play.matchup.pitcher.full_name.clone())
+        .unwrap_or_else(|| "Unknown pitcher".to_string())
+}
+
+impl PitcherSummary {
+    /// Total pitches thrown.
+    pub fn total(&self) -> u32 {
+        self.pitch_types.iter().map(|t| t.count).sum()
+    }
+
+    /// Pitches thrown in `category`.
+    pub fn category_total(&self, category: &str) -> u32 {
+        self.pitch_types
+            .iter()
+            .filter(|t| t.category == category)
+            .map(|t| t.count)
+            .sum()
+    }
+
+    /// Pitches thrown in the `balls`-`strikes` count.
+    pub fn at_count(&self, balls: u32, strikes: u32) -> u32 {
+        self.pitch_types.iter().map(|t| t.at_count(balls, strikes)).sum()
+    }
+
+    /// Pitches thrown in the counts of `bucket`.
+    pub fn in_bucket(&self, bucket: CountBucket) -> u32 {
+        self.pitch_types.iter().map(|t| t.in_bucket(bucket)).sum()
+    }
+
+    /// All pitches to left-handed batters.
+    pub fn vs_left(&self) -> Split {
+        let mut total = Split::default();
+        for t in &self.pitch_types {
+            total.merge(&t.vs_left);
+        }
+        total
+    }
+
+    /// All pitches to right-handed batters.
+    pub fn vs_right(&self) -> Split {
+        let mut total = Split::default();
+        for t in &self.pitch_types {
+            total.merge(&t.vs_right);
+        }
+        total
+    }
+
+    /// Outcomes of all pitches thrown.
+    pub fn outcomes(&self) -> Outcomes {
+        let mut total = Outcomes::default();
+        for t in &self.pitch_types {
+            total.merge(&t.outcomes);
+        }
+        total
+    }
+
+    /// Distinct categories, in order of first use.
+    pub fn categories(&self) -> Vec<&str> {
+        let mut cats: Vec<&str> = Vec::new();
+        for t in &self.pitch_types {
+            if !cats.contains(&t.category.as_str()) {
+                cats.push(&t.category);
+            }
+        }
+        cats
+    }
+
+    /// The pitch type with this name in this category.
+    pub fn pitch_type(&self, name: &str, category: &str) -> Option<&PitchTypeSummary> {
+        self.pitch_types.iter().find(|t| t.name == name && t.category == category)
+    }
+
+    /// Pitch types in `category`, most thrown first.
+    pub fn pitch_types_in(&self, category: &str) -> Vec<&PitchTypeSummary> {
+        let mut types: Vec<_> = self.pitch_types.iter().filter(|t| t.category == category).collect();
+        types.sort_by_key(|t| Reverse(t.count));
+        types
+    }
+
+    /// Largest distance, in inches, between the average release points of
+    /// any two pitch types. Small values mean every pitch comes out of the
+    /// same slot; a growing spread can point to tipping or fatigue.
+    pub fn release_spread(&self) -> Option<f64> {
+        let points: Vec<(f64, f64)> = self
+            .pitch_types
+            .iter()
+            .filter_map(|t| Some((t.release_side.mean()?, t.release_height.mean()?)))
+            .collect();
+        if points.len() < 2 {
+            return None;
+        }
+
+        let mut spread: f64 = 0.0;
+        for (i, a) in points.iter().enumerate() {
+            for b in &points[i + 1..] {
+                spread = spread.max((a.0 - b.0).hypot(a.1 - b.1) * 12.0);
+            }
+        }
+        Some(spread)
+    }
+
+    /// Pitches thrown up to and including each inning, in inning order.
+    pub fn cumulative_pitches(&self) -> Vec<u32> {
+        self.innings
+            .iter()
+            .scan(0, |total, inning| {
+                *total += inning.pitches;
+                Some(*total)
+            })
+            .collect()
+    }
+
+    fn inning_mut(&mut self, inning: u32, half: &str) -> &mut InningSummary {
+        if let Some(i) = self.innings.iter().position(|s| s.inning == inning && s.half == half) {
+            return &mut self.innings[i];
+        }
+        self.innings.push(InningSummary {
+            inning,
+            half: half.to_string(),
+            ..Default::default()
+        });
+        self.innings.last_mut().unwrap()
+    }
+
+    fn pitch_type_mut(&mut self, name: String, category: String) -> &mut PitchTypeSummary {
+        if let Some(i) = self.pitch_types.iter().position(|t| t.name == name && t.category == category) {
+            return &mut self.pitch_types[i];
+        }
+        self.pitch_types.push(PitchTypeSummary {
+            name,
+            category,
+            ..Default::default()
+        });
+        self.pitch_types.last_mut().unwrap()
+    }
+}
+
+impl PitchTypeSummary {
+    /// Pitches thrown in the `balls`-`strikes` count.
+    pub fn at_count(&self, balls: u32, strikes: u32) -> u32 {
+        self.by_count
+            .get(balls as usize)
+            .and_then(|row| row.get(strikes as usize))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Pitches thrown in the counts of `bucket`.
+    pub fn in_bucket(&self, bucket: CountBucket) -> u32 {
+        bucket.counts().iter().map(|(b, s)| self.at_count(*b, *s)).sum()
+    }
+
+    fn record(&mut self, ev: &PlayEvent, ctx: &PitchContext) {
+        self.count += 1;
+        let (balls, strikes) = (ctx.count.balls as usize, ctx.count.strikes as usize);
+        if balls < 4 && strikes < 3 {
+            self.by_count[balls][strikes] += 1;
+        }
+        let outcome = PitchOutcome::from_details(&ev.details);
+        if let Some(o) = outcome {
+            self.outcomes.add(o);
+        }
+        match ctx.bat_side {
+            Some("L") => self.vs_left.add(outcome),
+            Some("R") => self.vs_right.add(outcome),
+            _ => {}
+        }
+        if let Some(pd) = &ev.pitch_data {
+            self.start_speed.add_opt(pd.start_speed);
+            self.end_speed.add_opt(pd.end_speed);
+            self.spin_rate.add_opt(pd.breaks.spin_rate);
+            self.spin_axis.add_opt(pd.breaks.spin_direction);
+
+            // both axes use the full-flight breaks only: pfx covers the last 40 ft,
+            // roughly half the break, and would skew the mean if mixed in
+            let coords = &pd.coordinates;
+            // horizontal break is measured from the catcher's view: a right-hander's
+            // arm side is negative x, a left-hander's positive
+            let arm_side = match ctx.hand {
+                Some("R") => Some(-1.0),
+                Some("L") => Some(1.0),
+                _ => None,
+            };
+            if let (Some(sign), Some(x)) = (arm_side, pd.breaks.break_horizontal) {
+                self.horizontal_break.add(sign * x);
+            }
+            self.induced_vertical_break.add_opt(pd.breaks.break_vertical_induced);
+
+            self.release_height.add_opt(coords.z0);
+            self.release_side.add_opt(coords.x0);
+            self.extension.add_opt(pd.extension);
+        }
+    }
+}
+
+/// Summarize the pitches of every pitcher in the game by pitch type.
+///
+/// Pitch types are grouped into categories by `scheme`.
+pub fn summarize_pitches(feed: &GameFeed, scheme: &Scheme) -> Result<GameSummary> {
+    let mut summarizer = Summarizer::new(scheme.clone());
+    summarizer.update(feed, 0)?;
+    Ok(summarizer.into_summary())
+}
+
+/// Builds a [`GameSummary`] incrementally from successive versions of a live feed.
+///
+/// The caller says which plays changed since the last update (for a live feed,
+/// the lowest play index its JSON patches touch). When only the last play read
+/// changed, as it does while it is in progress, that play is taken back and read
+/// again; when an earlier play changed, the summary is rebuilt from the start.
+#[derive(Debug, Clone, Default)]
+pub struct Summarizer {
+    scheme: Scheme,
+    summary: GameSummary,
+    /// Plays read so far.
+    read: usize,
+    /// How to take back the last play read.
+    undo: Option<Undo>,
+}
+
+/// What reading one play changed in a summary. All of a play's pitches are
+/// thrown by its pitcher, so nothing else needs to be kept.
+#[derive(Debug, Clone)]
+struct Undo {
+    /// Length of `pitchers` before the play.
+    pitchers: usize,
+    /// Length of `unclassified` before the play.
+    unclassified: usize,
+    /// The play's pitcher before the play, if they had pitched already.
+    pitcher: Option<(usize, PitcherSummary)>,
+}
+
+impl Summarizer {
+    /// A summarizer that groups pitch types by `scheme`.
+    pub fn new(scheme: Scheme) -> Self {
+        Summarizer {
+            scheme,
+            ..Summarizer::default()
+        }
+    }
+
+    /// Bring the summary up to date with `feed`, whose first `unchanged` plays
+    /// are as they were at the previous update (`0` if unknown). Returns how many
+    /// pitches were added.
+    pub fn update(&mut self, feed: &GameFeed, unchanged: usize) -> Result<usize> {
+        check_game_state(feed)?;
+        let all_plays = feed.live_data.plays.all_plays.as_ref().ok_or_else(|| Error::Schema {
+            path: "liveData.plays.allPlays".to_string(),
+            message: "missing field".to_string(),
+        })?;
+
+        let before = self.summary.total_pitches();
+        let unchanged = unchanged.min(all_plays.len());
+        if unchanged < self.read {
+            match self.undo.take() {
+                Some(undo) if unchanged + 1 == self.read => self.take_back(undo),
+                _ => self.reset(),
+            }
+        }
+
+        let teams = &feed.game_data.teams;
+        self.summary.game_pk = feed.game_pk;
+        self.summary.away_team = teams.away.name.clone();
+        self.summary.home_team = teams.home.name.clone();
+        self.summary.categories = self.scheme.category_names();
+
+        for (i, play) in all_plays.iter().enumerate().skip(self.read) {
+            if i + 1 == all_plays.len() {
+                self.undo = Some(Undo {
+                    pitchers: self.summary.pitchers.len(),
+                    unclassified: self.summary.unclassified.len(),
+                    pitcher: self
+                        .summary
+                        .pitcher_position(feed, play)
+                        .map(|p| (p, self.summary.pitchers[p].clone())),
+                });
+            }
+            // each event's count is the count after it; pitches are filed under the count before
+            let mut count = Count::default();
+            for ev in &play.play_events {
+                if is_pitch_event(ev) {
+                    self.summary.record_pitch(&self.scheme, feed, play, ev, count);
+                }
+                if let Some(c) = ev.count {
+                    count = c;
+                }
+            }
+        }
+        self.read = all_plays.len();
+
+        Ok(self.summary.total_pitches().saturating_sub(before) as usize)
+    }
+
+    /// Take back the last play read.
+    fn take_back(&mut self, undo: Undo) {
+        self.summary.pitchers.truncate(undo.pitchers);
+        self.summary.unclassified.truncate(undo.unclassified);
+        if let Some((i, pitcher)) = undo.pitcher {
+            self.summary.pitchers[i] = pitcher;
+        }
+        self.read -= 1;
+    }
+
+    /// Forget everything read so far.
+    pub fn reset(&mut self) {
+        *self = Summarizer::new(self.scheme.clone());
+    }
+
+    /// The summary so far.
+    pub fn summary(&self) -> &GameSummary {
+        &self.summary
+    }
+
+    /// The summary so far, consuming the summarizer.
+    pub fn into_summary(self) -> GameSummary {
+        self.summary
+    }
+}
+
+/// Reject feeds of games that have no pitches to summarize.
+fn check_game_state(feed: &GameFeed) -> Result<(), Error> {
+    let status = &feed.game_data.status;
+    let state = status.detailed_state.clone().unwrap_or_else(|| "unknown state".to_string());
+    let no_plays = feed.live_data.plays.all_plays.as_ref().is_none_or(|p| p.is_empty());
+
+    // a game suspended after it started still has pitches to summarize
+    let halted = feed.halted_state().is_some_and(|s| no_plays || !s.to_lowercase().contains("suspended"));
+    if halted {
+        return Err(Error::GamePostponed {
+            game_pk: feed.game_pk,
+            state,
+        });
+    }
+    if status.abstract_game_state.as_deref() == Some("Preview") {
+        return Err(Error::GameNotStarted {
+            game_pk: feed.game_pk,
+            state,
+        });
+    }
+    Ok(())
+}
+
+/// Whether a play event is a pitch (as opposed to a pickoff, mound visit, ...).
+pub fn is_pitch_event(ev: &PlayEvent) -> bool {
+    ev.is_pitch.unwrap_or(ev.pitch_data.is_some())
+}
+
+/// The raw pitch type label of a pitch event: its statsapi code, or a
+/// description when the code is missing.
+pub fn find_pitch_type(ev: &PlayEvent) -> String {
+    let pitch_type = ev.details.pitch_type.as_ref();
+    if let Some(code) = pitch_type.and_then(|t| t.code.as_deref()).filter(|c| !c.trim().is_empty()) {
+        return code.trim().to_uppercase();
+    }
+    if let Some(t) = pitch_type.and_then(|t| t.description.as_deref()).filter(|d| !d.trim().is_empty()) {
+        return t.to_lowercase();
+    }
+
+    "unknown".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::model::{feed_from_value, parse_feed};
+
+    fn pitch(code: &str, call: &str, speed: Option<f64>) -> Value {
+        let mut ev = json!({
+            "isPitch": true,
+            "type": "pitch",
+            "details": { "code": call, "type": { "code": code } },
+        });
+        if let Some(speed) = speed {
+            ev["pitchData"] = json!({ "startSpeed": speed });
+        }
+        ev
+    }
+
+    fn play(inning: u32, events: Vec<Value>) -> Value {
+        json!({
+            "about": { "inning": inning, "halfInning": "top" },
+            "matchup": { "pitcher": { "id": 1, "fullName": "A Pitcher" }, "batSide": { "code": "R" } },
+            "playEvents": events,
+        })
+    }
+
+    fn feed(plays: Vec<Value>) -> GameFeed {
+        feed_from_value(&json!({
+            "gameData": { "status": { "abstractGameState": "Live", "detailedState": "In Progress" } },
+            "liveData": { "plays": { "allPlays": plays } },
+        }))
+        .unwrap()
+    }
+
+    fn assert_same(summarizer: &Summarizer, feed: &GameFeed) {
+        let full = summarize_pitches(feed, &Scheme::default()).unwrap();
+        assert_eq!(format!("{:?}", summarizer.summary()), format!("{:?}", full));
+    }
+
+    #[test]
+    fn count_buckets_cover_every_count_once() {
+        let mut all: Vec<(u32, u32)> = [CountBucket::Ahead, CountBucket::Even, CountBucket::Behind]
+            .iter()
+            .flat_map(|b| b.counts().iter().copied())
+            .collect();
+        all.sort();
+        let expected: Vec<(u32, u32)> = (0..4).flat_map(|b| (0..3).map(move |s| (b, s))).collect();
+        assert_eq!(all, expected);
+    }
+
+    #[test]
+    fn pitches_are_filed_under_the_count_before_them() {
+        // each event's count is the count after it
+        let pitch = |call: &str, balls: u32, strikes: u32| {
+            json!({
+                "isPitch": true,
+                "details": { "code": call, "type": { "code": "FF" } },
+                "count": { "balls": balls, "strikes": strikes },
+            })
+        };
+        let feed = parse_feed(
+            &json!({
+                "gameData": { "status": { "abstractGameState": "Live", "detailedState": "In Progress" } },
+                "liveData": { "plays": { "allPlays": [{
+                    "about": { "inning": 1, "halfInning": "top" },
+                    "matchup": { "pitcher": { "id": 1, "fullName": "A Pitcher" } },
+                    "playEvents": [pitch("B", 1, 0), pitch("C", 1, 1)],
+                }] } },
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let summary = summarize_pitches(&feed, &Scheme::default()).unwrap();
+        let pitcher = summary.pitcher(1).unwrap();
+        assert_eq!((pitcher.at_count(0, 0), pitcher.at_count(1, 0), pitcher.at_count(1, 1)), (1, 1, 0));
+        assert_eq!(pitcher.in_bucket(CountBucket::Even), 1);
+        assert_eq!(pitcher.in_bucket(CountBucket::Behind), 1);
+    }
+
+    #[test]
+    fn breaks_are_not_mixed_with_pfx() {
+        let mut with_breaks = pitch("FF", "B", Some(95.0));
+        with_breaks["pitchData"]["breaks"] = json!({ "breakHorizontal": -8.0, "breakVerticalInduced": 16.0 });
+        with_breaks["pitchData"]["coordinates"] = json!({ "pfxX": -4.0, "pfxZ": 8.0 });
+        let mut pfx_only = pitch("FF", "B", Some(95.0));
+        pfx_only["pitchData"]["coordinates"] = json!({ "pfxX": -4.0, "pfxZ": 8.0 });
+        let mut play = play(1, vec![with_breaks, pfx_only]);
+        play["matchup"]["pitchHand"] = json!({ "code": "R" });
+
+        let summary = summarize_pitches(&feed(vec![play]), &Scheme::default()).unwrap();
+        let fastball = summary.pitcher(1).unwrap().pitch_type("four-seam fastball", "heater").unwrap();
+        assert_eq!(fastball.count, 2);
+        assert_eq!((fastball.horizontal_break.count(), fastball.horizontal_break.mean()), (1, Some(8.0)));
+        assert_eq!(fastball.induced_vertical_break.mean(), Some(16.0));
+    }
+
+    #[test]
+    fn update_rereads_revised_events_of_the_current_play() {
+        let mut summarizer = Summarizer::new(Scheme::default());
+        summarizer.update(&feed(vec![play(1, vec![pitch("SL", "B", None)])]), 0).unwrap();
+
+        // the pitch is reclassified, its pitch data and call arrive, and a new pitch follows
+        let revised = feed(vec![play(1, vec![pitch("CU", "S", Some(78.0)), pitch("FF", "F", Some(95.0))])]);
+        assert_eq!(summarizer.update(&revised, 0).unwrap(), 1);
+        assert_same(&summarizer, &revised);
+
+        // and again, with a new pitcher taking over in a new play
+        let mut relief = play(2, vec![pitch("Vulcan", "B", Some(80.0))]);
+        relief["matchup"]["pitcher"] = json!({ "id": 2, "fullName": "B Pitcher" });
+        let first = play(1, vec![pitch("CU", "S", Some(79.0)), pitch("FF", "F", Some(95.0))]);
+        let next = feed(vec![first.clone(), relief.clone()]);
+        summarizer.update(&next, 0).unwrap();
+        assert_same(&summarizer, &next);
+
+        // only the relief pitcher's play changed
+        relief["playEvents"][0] = pitch("SL", "C", Some(84.0));
+        let next = feed(vec![first, relief]);
+        summarizer.update(&next, 1).unwrap();
+        assert_same(&summarizer, &next);
+    }
+
+    #[test]
+    fn update_rebuilds_when_an_earlier_play_changes() {
+        let mut summarizer = Summarizer::new(Scheme::default());
+        summarizer
+            .update(
+                &feed(vec![
+                    play(1, vec![pitch("SL", "B", None)]),
+                    play(1, vec![pitch("FF", "C", Some(94.0))]),
+                ]),
+                0,
+            )
+            .unwrap();
+
+        let revised = feed(vec![
+            play(1, vec![pitch("CH", "B", Some(85.0))]),
+            play(1, vec![pitch("FF", "C", Some(94.0))]),
+            play(2, vec![pitch("SI", "X", Some(93.0))]),
+        ]);
+        summarizer.update(&revised, 0).unwrap();
+        assert_same(&summarizer, &revised);
+    }
+
+    #[test]
+    fn update_reads_only_what_changed() {
+        let first = play(1, vec![pitch("SL", "B", None)]);
+        let mut summarizer = Summarizer::new(Scheme::default());
+        summarizer.update(&feed(vec![first.clone()]), 0).unwrap();
+
+        // the first play is left alone when reported unchanged
+        let revised = feed(vec![play(1, vec![pitch("CH", "B", None)]), play(2, vec![pitch("FF", "C", None)])]);
+        assert_eq!(summarizer.update(&revised, 1).unwrap(), 1);
+        assert_same(&summarizer, &feed(vec![first, play(2, vec![pitch("FF", "C", None)])]));
+
+        // nothing changed
+        assert_eq!(summarizer.update(&revised, usize::MAX).unwrap(), 0);
+    }
+}
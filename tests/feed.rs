@@ -0,0 +1,104 @@
+//! Summaries of a saved game feed (`tests/fixtures/feed.json`: a made-up
+//! two-inning game, Riverside Otters at Harbor City Gulls).
+
+use std::path::Path;
+
+use pitchers::{load_feed, summarize_pitches, CountBucket, GameSummary, Scheme, Side};
+
+fn fixture() -> GameSummary {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/feed.json");
+    let feed = load_feed(&path).unwrap();
+    summarize_pitches(&feed, &Scheme::default()).unwrap()
+}
+
+#[test]
+fn pitchers_are_grouped_by_team_in_order_of_appearance() {
+    let summary = fixture();
+    assert_eq!(summary.game_pk, Some(700001));
+    assert_eq!(summary.team_name(Side::Away), Some("Riverside Otters"));
+    assert_eq!(summary.team_name(Side::Home), Some("Harbor City Gulls"));
+
+    let staff = |side| -> Vec<(&str, u32)> {
+        summary.staff(side).iter().map(|p| (p.name.as_str(), p.total())).collect()
+    };
+    assert_eq!(staff(Side::Home), [("Sam Ortega", 7), ("Eli Brandt", 6)]);
+    assert_eq!(staff(Side::Away), [("Jonah Reyes", 7)]);
+    assert_eq!(summary.total_pitches(), 20);
+}
+
+#[test]
+fn pitch_types_are_categorized() {
+    let summary = fixture();
+    let ortega = summary.pitcher(900101).unwrap();
+    assert_eq!(ortega.hand.as_deref(), Some("R"));
+    assert_eq!(ortega.categories(), ["heater", "breaking ball", "offspeed"]);
+    assert_eq!(ortega.category_total("heater"), 4);
+    // the curve has only a description
+    assert_eq!(ortega.pitch_type("knuckle curve", "breaking ball").unwrap().count, 1);
+
+    // a pitchout is not a pitch type of its own
+    let brandt = summary.pitcher(900102).unwrap();
+    assert_eq!(brandt.pitch_type("pitchout", "other").unwrap().count, 1);
+
+    // a pitch without any type is "unknown", a label no pitch type matches is kept as it came
+    let reyes = summary.pitcher(900201).unwrap();
+    assert_eq!(reyes.pitch_type("unknown", "other").unwrap().count, 1);
+    assert_eq!(reyes.pitch_type("vulcan", "unclassified").unwrap().count, 1);
+    assert_eq!(summary.unclassified, ["vulcan"]);
+    assert_eq!(summary.team_category_total(Side::Away, "unclassified"), 1);
+}
+
+#[test]
+fn outcomes_counts_and_splits() {
+    let summary = fixture();
+    let ortega = summary.pitcher(900101).unwrap();
+
+    let outcomes = ortega.outcomes();
+    assert_eq!(
+        (outcomes.balls, outcomes.called_strikes, outcomes.swinging_strikes, outcomes.fouls, outcomes.in_play),
+        (2, 1, 3, 0, 1)
+    );
+    assert_eq!(outcomes.whiff_rate(), Some(0.75));
+
+    // pitches are filed under the count before they were thrown; the mound visit is not a pitch
+    assert_eq!(ortega.at_count(0, 0), 2);
+    assert_eq!(ortega.at_count(1, 1), 2);
+    assert_eq!(ortega.in_bucket(CountBucket::Ahead), 2);
+    assert_eq!(ortega.in_bucket(CountBucket::Even), 4);
+    assert_eq!(ortega.in_bucket(CountBucket::Behind), 1);
+
+    assert_eq!(ortega.vs_right().count, 4);
+    assert_eq!(ortega.vs_left().count, 3);
+    assert_eq!(summary.team_outcomes(Side::Home).total(), 13);
+}
+
+#[test]
+fn pitch_metrics() {
+    let summary = fixture();
+    let ortega = summary.pitcher(900101).unwrap();
+    let fastball = ortega.pitch_type("four-seam fastball", "heater").unwrap();
+    assert_eq!(fastball.start_speed.mean(), Some(95.0));
+    assert_eq!(fastball.start_speed.min(), Some(94.0));
+    assert_eq!(fastball.start_speed.max(), Some(96.0));
+    assert_eq!(fastball.induced_vertical_break.mean(), Some(16.0));
+    // a right-hander's arm side is negative x in the feed, positive here
+    assert_eq!(fastball.horizontal_break.mean(), Some(8.0));
+    assert_eq!(fastball.spin_rate.mean(), Some(2350.0));
+    assert_eq!(fastball.extension.mean(), Some(6.5));
+
+    // each pitch type leaves from its own spot; the slider and the curve are furthest apart
+    let slider = ortega.pitch_type("slider", "breaking ball").unwrap();
+    assert_eq!((slider.release_side.mean(), slider.release_height.mean()), (Some(-1.75), Some(5.6)));
+    let spread = ortega.release_spread().unwrap();
+    assert!((spread - 4.24).abs() < 0.01, "release spread {}", spread);
+
+    // and a left-hander's is positive in both
+    let brandt = summary.pitcher(900102).unwrap();
+    let sinker = brandt.pitch_type("sinker", "heater").unwrap();
+    assert_eq!(sinker.horizontal_break.mean(), Some(15.5));
+
+    // the pitch without pitch data adds to the count only
+    let reyes = summary.pitcher(900201).unwrap();
+    let unknown = reyes.pitch_type("unknown", "other").unwrap();
+    assert_eq!(unknown.start_speed.count(), 0);
+}